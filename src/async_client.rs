@@ -0,0 +1,235 @@
+use crate::builder::ClientConfig;
+use crate::conditional::ConditionalQuestion;
+use crate::query::{QuestionPage, QuestionQuery};
+use crate::transport::{AsyncTransport, HttpRequest, ReqwestTransport};
+use crate::{posts, ApiVersion, MetaculusBuilder, MetaculusError, Prediction, Question};
+use chrono::NaiveDateTime;
+use futures::stream::{self, Stream};
+use log::info;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+///
+/// A non-blocking API client for retrieving Metaculus question data, with the same methods as
+/// [Metaculus](crate::Metaculus). Requires the `async` feature.
+///
+/// # Example
+///
+/// ``` rust,no_run
+/// use metaculustetra::AsyncMetaculus;
+///
+/// # async fn run() {
+/// let m = AsyncMetaculus::standard();
+/// let prediction = m.get_prediction_for("7887").await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncMetaculus {
+    config: ClientConfig,
+    transport: Arc<dyn AsyncTransport>,
+}
+
+impl AsyncMetaculus {
+    pub(crate) fn new(config: ClientConfig, transport: Arc<dyn AsyncTransport>) -> AsyncMetaculus {
+        AsyncMetaculus { config, transport }
+    }
+
+    ///
+    /// Returns a default async Metaculus instance that retrieves questions from
+    /// <https://www.metaculus.com> with a newly created [ReqwestTransport].
+    ///
+    pub fn standard() -> AsyncMetaculus {
+        AsyncMetaculus::with_domain("www")
+    }
+
+    ///
+    /// Returns an async Metaculus instance that retrieves questions from the given Metaculus
+    /// [domain](https://www.metaculus.com/news/2019/08/04/introducing-the-domain-system/), such as
+    /// `www` (Metaculus Prime), `pandemic`, or `ai`.
+    ///
+    pub fn with_domain(domain: &str) -> AsyncMetaculus {
+        AsyncMetaculus::new(
+            ClientConfig::for_domain(domain),
+            Arc::new(ReqwestTransport::default()),
+        )
+    }
+
+    ///
+    /// Returns a [MetaculusBuilder] for configuring a new client, which can be finished with
+    /// [MetaculusBuilder::build_async].
+    ///
+    pub fn builder() -> MetaculusBuilder {
+        MetaculusBuilder::default()
+    }
+
+    /// Returns the base URL that API paths are appended to.
+    pub fn base_url(&self) -> &str {
+        &self.config.base_url
+    }
+
+    /// Returns the generation of the Metaculus API that questions are requested from.
+    pub fn api_version(&self) -> ApiVersion {
+        self.config.api_version
+    }
+
+    ///
+    /// Returns the numeric prediction for the question `id` if it is a numerical question, and
+    /// `None` otherwise.
+    ///
+    pub async fn get_numeric_prediction_for(&self, id: &str) -> Option<f64> {
+        self.try_get_numeric_prediction_for(id).await.ok()?
+    }
+
+    ///
+    /// Returns the numeric prediction for the question `id` if it is a numerical question, `None`
+    /// if it is not numerical or has no predictions, and a [MetaculusError] if the question could
+    /// not be retrieved.
+    ///
+    pub async fn try_get_numeric_prediction_for(
+        &self,
+        id: &str,
+    ) -> Result<Option<f64>, MetaculusError> {
+        Ok(self
+            .try_get_prediction_for(id)
+            .await?
+            .and_then(|p| p.get_if_numeric()))
+    }
+
+    ///
+    /// Returns the date prediction for the question `id` if it is a date question, and `None`
+    /// otherwise.
+    ///
+    pub async fn get_date_prediction_for(&self, id: &str) -> Option<NaiveDateTime> {
+        self.try_get_date_prediction_for(id).await.ok()?
+    }
+
+    ///
+    /// Returns the date prediction for the question `id` if it is a date question, `None` if it is
+    /// not a date question or has no predictions, and a [MetaculusError] if the question could not
+    /// be retrieved.
+    ///
+    pub async fn try_get_date_prediction_for(
+        &self,
+        id: &str,
+    ) -> Result<Option<NaiveDateTime>, MetaculusError> {
+        Ok(self
+            .try_get_prediction_for(id)
+            .await?
+            .and_then(|p| p.get_if_date()))
+    }
+
+    ///
+    /// Returns the best prediction available (prioritising the actual resolution, then the Metaculus
+    /// prediction, then the community prediction) for the question with id `id` as a [Prediction],
+    /// if the question exists and has any predictions.
+    ///
+    pub async fn get_prediction_for(&self, id: &str) -> Option<Prediction> {
+        self.try_get_prediction_for(id).await.ok()?
+    }
+
+    ///
+    /// Returns the best prediction available for the question with id `id` as a [Prediction], `None`
+    /// if the question has no predictions, and a [MetaculusError] if the question could not be
+    /// retrieved.
+    ///
+    pub async fn try_get_prediction_for(
+        &self,
+        id: &str,
+    ) -> Result<Option<Prediction>, MetaculusError> {
+        Ok(self.try_get_question(id).await?.get_best_prediction())
+    }
+
+    ///
+    /// Returns the question with id `id` as a [Question] if it exists.
+    ///
+    pub async fn get_question(&self, id: &str) -> Option<Question> {
+        self.try_get_question(id).await.ok()
+    }
+
+    ///
+    /// Returns the question with id `id` as a [Question], or a [MetaculusError] describing why it
+    /// could not be retrieved.
+    ///
+    pub async fn try_get_question(&self, id: &str) -> Result<Question, MetaculusError> {
+        let payload = self.get_json(&self.config.question_url(id), id).await?;
+        let question = posts::question_from_value(payload, self.config.api_version)?;
+        info!("Question id {} retrieved successfully.", id);
+
+        Ok(question)
+    }
+
+    ///
+    /// Returns the conditional question pair with id `id` as a [ConditionalQuestion] if it exists.
+    ///
+    pub async fn get_conditional(&self, id: &str) -> Option<ConditionalQuestion> {
+        self.try_get_conditional(id).await.ok()
+    }
+
+    ///
+    /// Returns the conditional question pair with id `id` as a [ConditionalQuestion], or a
+    /// [MetaculusError] describing why it could not be retrieved.
+    ///
+    pub async fn try_get_conditional(
+        &self,
+        id: &str,
+    ) -> Result<ConditionalQuestion, MetaculusError> {
+        let payload = self.get_json(&self.config.question_url(id), id).await?;
+        let conditional = posts::conditional_from_value(payload, self.config.api_version)?;
+        info!("Conditional question id {} retrieved successfully.", id);
+
+        Ok(conditional)
+    }
+
+    ///
+    /// Returns a lazy stream of the questions matching `query`, transparently following the
+    /// pagination links returned by Metaculus. If a page cannot be retrieved, the stream returns
+    /// the [MetaculusError] and then ends.
+    ///
+    pub fn list_questions(
+        &self,
+        query: &QuestionQuery,
+    ) -> impl Stream<Item = Result<Question, MetaculusError>> + '_ {
+        let state = (Some(self.config.list_url(query)), VecDeque::new());
+
+        stream::unfold(state, move |(mut next_url, mut questions)| async move {
+            while questions.is_empty() {
+                let url = next_url.take()?;
+                match self.get_json::<QuestionPage>(&url, &url).await {
+                    Ok(page) => {
+                        next_url = page.next.and_then(|next| self.config.next_page_url(&next));
+                        questions.extend(page.results);
+                    }
+                    Err(e) => return Some((Err(e), (None, questions))),
+                }
+            }
+
+            let question =
+                posts::question_from_value(questions.pop_front()?, self.config.api_version);
+            Some((question, (next_url, questions)))
+        })
+    }
+
+    ///
+    /// Returns a client with the same settings and transport for the given Metaculus `domain`, as
+    /// described in [ClientConfig::on_domain].
+    ///
+    pub(crate) fn on_domain(&self, domain: &str) -> Result<AsyncMetaculus, MetaculusError> {
+        Ok(AsyncMetaculus::new(
+            self.config.on_domain(domain)?,
+            self.transport.clone(),
+        ))
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        id: &str,
+    ) -> Result<T, MetaculusError> {
+        let request = HttpRequest {
+            url: url.to_string(),
+            headers: self.config.headers(),
+        };
+        self.transport.get(&request).await?.into_json(id)
+    }
+}
@@ -0,0 +1,159 @@
+//!
+//! A module for checking how well calibrated the community and Metaculus predictions are over a set
+//! of resolved binary questions: of the questions given a probability of around 70%, did around 70%
+//! resolve yes?
+//!
+//! # Example
+//! ```rust
+//! use chrono::Duration;
+//! use metaculustetra::calibration::calibrate;
+//! use metaculustetra::scoring::Aggregate;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//! use metaculustetra::Question;
+//!
+//! let file = File::open("tests/resolved_probability_example.json").unwrap();
+//! let question: Question = serde_json::from_reader(BufReader::new(file)).unwrap();
+//!
+//! // Community predictions a week before close, in ten buckets of 10%
+//! let report = calibrate(&[question], Aggregate::Community, Duration::weeks(1), 10);
+//! assert_eq!(report.count, 1);
+//! assert_eq!(report.buckets[0].observed_frequency, 1.0);
+//! ```
+
+use crate::scoring::Aggregate;
+use crate::{NumP, Question};
+use chrono::Duration;
+
+/// The z-score of the 95% confidence intervals reported for each bucket.
+const Z_95: f64 = 1.959964;
+
+///
+/// The calibration of a set of predictions, created with [calibrate].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationReport {
+    /// The buckets that contain at least one prediction, in increasing order of probability.
+    pub buckets: Vec<CalibrationBucket>,
+    /// The number of questions that were included.
+    pub count: usize,
+    ///
+    /// The expected calibration error: the average distance between the mean prediction and the
+    /// observed frequency in each bucket, weighted by the number of predictions in the bucket.
+    ///
+    pub calibration_error: f64,
+}
+
+///
+/// The predictions that fell in a range of probabilities, and how often the questions they were
+/// made on resolved yes.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationBucket {
+    /// The lowest probability in the bucket.
+    pub lower: f64,
+    /// The highest probability in the bucket.
+    pub upper: f64,
+    /// The number of predictions in the bucket.
+    pub count: usize,
+    /// The mean of the predictions in the bucket.
+    pub mean_prediction: f64,
+    /// The proportion of questions in the bucket that resolved yes.
+    pub observed_frequency: f64,
+    /// The 95% Wilson score interval for the observed frequency.
+    pub confidence_interval: (f64, f64),
+}
+
+///
+/// Buckets the `aggregate` predictions made `offset` before each question closed into `buckets`
+/// equal ranges of probability, and reports how often the questions in each range resolved yes.
+///
+/// Questions are left out if they are not binary, have not resolved, resolved ambiguously, were
+/// annulled, have no close time, or had no prediction at that time.
+///
+pub fn calibrate(
+    questions: &[Question],
+    aggregate: Aggregate,
+    offset: Duration,
+    buckets: usize,
+) -> CalibrationReport {
+    let buckets = buckets.max(1);
+    let mut predictions = vec![Vec::new(); buckets];
+
+    for (prediction, outcome) in questions
+        .iter()
+        .filter_map(|q| prediction_and_outcome(q, aggregate, offset))
+    {
+        let i = ((prediction * buckets as f64) as usize).min(buckets - 1);
+        predictions[i].push((prediction, outcome));
+    }
+
+    let count = predictions.iter().map(Vec::len).sum();
+    let buckets: Vec<CalibrationBucket> = predictions
+        .iter()
+        .enumerate()
+        .filter(|(_, bucket)| !bucket.is_empty())
+        .map(|(i, bucket)| {
+            let n = bucket.len() as f64;
+            let observed_frequency = bucket.iter().map(|(_, o)| o).sum::<f64>() / n;
+            CalibrationBucket {
+                lower: i as f64 / buckets as f64,
+                upper: (i + 1) as f64 / buckets as f64,
+                count: bucket.len(),
+                mean_prediction: bucket.iter().map(|(p, _)| p).sum::<f64>() / n,
+                observed_frequency,
+                confidence_interval: wilson_interval(observed_frequency, n),
+            }
+        })
+        .collect();
+
+    let calibration_error = if count == 0 {
+        0.0
+    } else {
+        buckets
+            .iter()
+            .map(|b| b.count as f64 * (b.mean_prediction - b.observed_frequency).abs())
+            .sum::<f64>()
+            / count as f64
+    };
+
+    CalibrationReport {
+        buckets,
+        count,
+        calibration_error,
+    }
+}
+
+/// Returns the prediction made `offset` before the question closed, and how it resolved.
+fn prediction_and_outcome(
+    question: &Question,
+    aggregate: Aggregate,
+    offset: Duration,
+) -> Option<(f64, f64)> {
+    if !question.is_binary() {
+        return None;
+    }
+    // Ambiguous and annulled resolutions say nothing about calibration, so they are left out.
+    let outcome = match question.get_resolution()? {
+        NumP(outcome) => outcome,
+        _ => return None,
+    };
+
+    let date = question.close_time? - offset;
+    let prediction = match aggregate {
+        Aggregate::Community => question.get_community_prediction_before(date)?,
+        Aggregate::Metaculus => question.get_metaculus_prediction_before(date)?,
+    };
+    Some((prediction.get_if_numeric()?, outcome))
+}
+
+/// Returns the 95% Wilson score interval for a proportion `p` observed in `n` trials.
+fn wilson_interval(p: f64, n: f64) -> (f64, f64) {
+    let z2 = Z_95 * Z_95;
+    let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half_width = Z_95 / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    (
+        (centre - half_width).max(0.0),
+        (centre + half_width).min(1.0),
+    )
+}
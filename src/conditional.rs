@@ -0,0 +1,145 @@
+//!
+//! A module for conditional questions, which link a condition question and an outcome question to
+//! forecast the outcome both if the condition happens and if it does not.
+//!
+
+use crate::date_utils::DateUtils;
+use crate::error::from_json;
+use crate::posts;
+use crate::{AmbP, Annulled, ApiVersion, MetaculusError, NumP, Prediction, Question};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+///
+/// A conditional question pair, retrieved with
+/// [Metaculus::get_conditional](crate::Metaculus::get_conditional).
+///
+/// Metaculus represents the pair as two binary questions, one asking about the outcome if the
+/// condition resolves yes and one if it resolves no. Once the condition resolves, the branch that
+/// did not happen is annulled and the other resolves the same way as the outcome.
+///
+/// # Example
+/// ``` rust,no_run
+/// use metaculustetra::Metaculus;
+///
+/// let m = Metaculus::standard();
+/// let conditional = m.get_conditional("8536").unwrap();
+/// println!(
+///     "P({} | {}) = {:?}",
+///     conditional.outcome.title_short,
+///     conditional.condition.title_short,
+///     conditional.get_probability_if_yes()
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConditionalQuestion {
+    /// The id of the conditional pair.
+    #[serde(default)]
+    pub id: u64,
+    /// The question the pair is conditioned on.
+    pub condition: Question,
+    /// The question whose outcome is forecast.
+    #[serde(rename = "condition_child")]
+    pub outcome: Question,
+    /// The outcome question, if the condition resolves yes.
+    #[serde(rename = "question_yes")]
+    pub if_yes: Question,
+    /// The outcome question, if the condition resolves no.
+    #[serde(rename = "question_no")]
+    pub if_no: Question,
+}
+
+/// The two branches of a [ConditionalQuestion].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Branch {
+    /// The condition resolves yes.
+    IfYes,
+    /// The condition resolves no.
+    IfNo,
+}
+
+impl ConditionalQuestion {
+    ///
+    /// Parses a conditional pair from a JSON response `body` from the given generation of the
+    /// Metaculus API. [ApiVersion::Auto] accepts either generation.
+    ///
+    pub fn from_json(body: &str, version: ApiVersion) -> Result<Self, MetaculusError> {
+        posts::conditional_from_value(from_json(body)?, version)
+    }
+
+    ///
+    /// Returns the best prediction available for the probability of the outcome if the condition
+    /// resolves yes, if there is one. This is `None` once the condition has resolved no.
+    ///
+    pub fn get_probability_if_yes(&self) -> Option<f64> {
+        self.get_probability_if_yes_before(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Returns the best prediction available for the probability of the outcome if the condition
+    /// resolves yes, as it was on the given `date`.
+    ///
+    pub fn get_probability_if_yes_before(&self, date: NaiveDateTime) -> Option<f64> {
+        self.if_yes
+            .get_best_prediction_before(date)?
+            .get_if_numeric()
+    }
+
+    ///
+    /// Returns the best prediction available for the probability of the outcome if the condition
+    /// resolves no, if there is one. This is `None` once the condition has resolved yes.
+    ///
+    pub fn get_probability_if_no(&self) -> Option<f64> {
+        self.get_probability_if_no_before(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Returns the best prediction available for the probability of the outcome if the condition
+    /// resolves no, as it was on the given `date`.
+    ///
+    pub fn get_probability_if_no_before(&self, date: NaiveDateTime) -> Option<f64> {
+        self.if_no
+            .get_best_prediction_before(date)?
+            .get_if_numeric()
+    }
+
+    /// Returns the question for the given `branch`.
+    pub fn branch(&self, branch: Branch) -> &Question {
+        match branch {
+            Branch::IfYes => &self.if_yes,
+            Branch::IfNo => &self.if_no,
+        }
+    }
+
+    ///
+    /// Returns the branch that happened, if the condition has resolved yes or no. The histories of
+    /// each branch are available from [ConditionalQuestion::branch], such as with
+    /// `conditional.branch(Branch::IfYes).community_timeseries()`.
+    ///
+    pub fn get_condition_resolution(&self) -> Option<Branch> {
+        match self.condition.get_resolution()? {
+            NumP(1.0) => Some(Branch::IfYes),
+            NumP(0.0) => Some(Branch::IfNo),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the resolution of the pair: `None` until the condition has resolved,
+    /// [Prediction::AmbP] or [Prediction::Annulled] if the condition resolved ambiguously or was
+    /// annulled, and otherwise the resolution of the branch that happened (or of the outcome
+    /// question, if that branch has not caught up yet).
+    ///
+    pub fn get_resolution(&self) -> Option<Prediction> {
+        let condition = self.condition.get_resolution()?;
+        if matches!(condition, AmbP | Annulled) {
+            return Some(condition);
+        }
+
+        let branch = self.branch(self.get_condition_resolution()?);
+        branch
+            .get_resolution()
+            .filter(|r| !matches!(r, AmbP | Annulled))
+            .or_else(|| self.outcome.get_resolution())
+    }
+}
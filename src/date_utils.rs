@@ -1,8 +1,9 @@
-use chrono::{NaiveDate, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
 pub(crate) trait DateUtils {
     fn latest_prediction_date() -> NaiveDateTime;
     fn date_to_timestamp(date: &str) -> Option<f64>;
+    fn timestamp_to_date(timestamp: f64) -> Option<NaiveDateTime>;
 }
 
 impl DateUtils for NaiveDateTime {
@@ -11,17 +12,31 @@ impl DateUtils for NaiveDateTime {
     }
 
     ///
-    /// Converts a date in the format returned by Metaculus (`YYYY-MM-DD`) into a number of non-leap
-    /// seconds since midnight, January 1st, 1970, or `None` if the string is not a properly
-    /// formatted date.
+    /// Converts a date in either format returned by Metaculus (`YYYY-MM-DD` or RFC 3339, such as
+    /// `2021-09-02T21:23:04Z`) into a number of non-leap seconds since midnight, January 1st, 1970,
+    /// or `None` if the string is not a properly formatted date.
     ///
     fn date_to_timestamp(date: &str) -> Option<f64> {
+        if let Ok(time) = DateTime::parse_from_rfc3339(date) {
+            return Some(time.timestamp() as f64);
+        }
+
         let date_format = "%Y-%m-%d";
         Some(
             NaiveDate::parse_from_str(date, date_format)
                 .ok()?
                 .and_hms_opt(0, 0, 0)?
+                .and_utc()
                 .timestamp() as f64,
         )
     }
+
+    ///
+    /// Converts a number of seconds since midnight, January 1st, 1970 (as used for the `t` field of
+    /// Metaculus timeseries) into a date, or `None` if it is out of range. The date is rounded up
+    /// to the next microsecond, so that it is never before the timestamp.
+    ///
+    fn timestamp_to_date(timestamp: f64) -> Option<NaiveDateTime> {
+        Some(DateTime::from_timestamp_micros((timestamp * 1e6).ceil() as i64)?.naive_utc())
+    }
 }
@@ -0,0 +1,701 @@
+//!
+//! Probability distributions over the outcomes of questions, in the logistic mixture form used by
+//! Metaculus for continuous questions and as summarised in the prediction timeseries.
+//!
+
+use crate::scale::Scale;
+use crate::Prediction;
+use crate::Prediction::NumP;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+///
+/// A single logistic component of a [LogisticMixture], on the question's normalised scale (where
+/// `0.0` is the lower bound and `1.0` is the upper bound).
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct LogisticComponent {
+    /// The centre of the component.
+    pub x0: f64,
+    /// The scale (spread) of the component, which must be positive.
+    pub s: f64,
+    /// The weight of the component relative to the other components in its mixture.
+    #[serde(default = "default_weight")]
+    pub w: f64,
+}
+
+impl LogisticComponent {
+    ///
+    /// Returns the cumulative probability of the component at `x`, on the normalised scale, ignoring
+    /// the bounds of the question.
+    ///
+    pub fn cdf(&self, x: f64) -> f64 {
+        1.0 / (1.0 + (-(x - self.x0) / self.s).exp())
+    }
+
+    ///
+    /// Returns the probability density of the component at `x`, on the normalised scale, ignoring
+    /// the bounds of the question.
+    ///
+    pub fn pdf(&self, x: f64) -> f64 {
+        let e = (-(x - self.x0) / self.s).exp();
+        e / (self.s * (1.0 + e).powi(2))
+    }
+
+    ///
+    /// Returns the point on the normalised scale that the component is at most with probability
+    /// `p`, ignoring the bounds of the question.
+    ///
+    pub fn quantile(&self, p: f64) -> f64 {
+        self.x0 + self.s * (p / (1.0 - p)).ln()
+    }
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+///
+/// A weighted mixture of logistic distributions, as used by Metaculus to represent forecasts on
+/// continuous questions.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(from = "MixtureRepr", into = "MultiMixture")]
+pub struct LogisticMixture {
+    /// The components of the mixture.
+    pub components: Vec<LogisticComponent>,
+}
+
+impl LogisticMixture {
+    /// Makes a new `LogisticMixture` from its components.
+    pub fn new(components: Vec<LogisticComponent>) -> LogisticMixture {
+        LogisticMixture { components }
+    }
+
+    /// Returns the sum of the weights of the components.
+    pub fn total_weight(&self) -> f64 {
+        self.components.iter().map(|c| c.w).sum()
+    }
+}
+
+/// The shapes Metaculus uses for mixtures: a `multi` object, a bare list, or a single component.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MixtureRepr {
+    Multi { d: Vec<LogisticComponent> },
+    List(Vec<LogisticComponent>),
+    Single(LogisticComponent),
+}
+
+impl From<MixtureRepr> for LogisticMixture {
+    fn from(repr: MixtureRepr) -> Self {
+        match repr {
+            MixtureRepr::Multi { d } => LogisticMixture::new(d),
+            MixtureRepr::List(d) => LogisticMixture::new(d),
+            MixtureRepr::Single(c) => LogisticMixture::new(vec![c]),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MultiMixture {
+    kind: &'static str,
+    d: Vec<LogisticComponent>,
+}
+
+impl From<LogisticMixture> for MultiMixture {
+    fn from(mixture: LogisticMixture) -> Self {
+        MultiMixture {
+            kind: "multi",
+            d: mixture.components,
+        }
+    }
+}
+
+///
+/// An aggregate probability distribution over the outcome of a continuous question, such as the
+/// community prediction, built from the density Metaculus reports at evenly spaced points along
+/// the question's scale and the probability it assigns beyond each bound.
+///
+/// All values are in real units: [Prediction::NumP] for numeric questions and [Prediction::DatP]
+/// for date questions, on a logarithmic scale if the question has one. Metaculus does not report
+/// the shape of the distribution beyond the bounds of the scale, so only the total probability
+/// there is available.
+///
+/// # Example
+/// ```rust
+/// use metaculustetra::Prediction::NumP;
+/// use std::fs::File;
+/// use std::io::BufReader;
+/// use metaculustetra::Question;
+///
+/// let file = File::open("tests/resolved_range_example.json").unwrap();
+/// let question: Question = serde_json::from_reader(BufReader::new(file)).unwrap();
+/// let distribution = question.get_community_distribution().unwrap();
+///
+/// // Probability that GDP growth was negative
+/// let p = distribution.cdf(&NumP(0.0)).unwrap();
+/// assert!(p > 0.05 && p < 0.06);
+/// // 90% interval
+/// let lower = distribution.quantile(0.05).unwrap();
+/// let upper = distribution.quantile(0.95).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinuousDistribution {
+    pdf: Vec<f64>,
+    cdf: Vec<f64>,
+    low: f64,
+    high: f64,
+    scale: Scale,
+}
+
+impl ContinuousDistribution {
+    ///
+    /// Makes a new `ContinuousDistribution` from the density `y` at evenly spaced points on the
+    /// normalised scale, and the cumulative probabilities `low` and `high` at the bounds, or `None`
+    /// if there are too few points to describe a distribution.
+    ///
+    pub(crate) fn new(y: &[f64], low: f64, high: f64, scale: Scale) -> Option<Self> {
+        if y.len() < 2 {
+            return None;
+        }
+        let step = 1.0 / (y.len() - 1) as f64;
+
+        let mut cumulative = vec![0.0];
+        for pair in y.windows(2) {
+            cumulative.push(cumulative.last()? + (pair[0] + pair[1]) / 2.0 * step);
+        }
+        let total = *cumulative.last()?;
+        if total <= 0.0 {
+            return None;
+        }
+
+        // The reported density does not always integrate to exactly `high - low`, so rescale it.
+        let mass = high - low;
+        Some(ContinuousDistribution {
+            pdf: y.iter().map(|d| d * mass / total).collect(),
+            cdf: cumulative.iter().map(|c| low + c * mass / total).collect(),
+            low,
+            high,
+            scale,
+        })
+    }
+
+    ///
+    /// Returns the probability density at `x` per real unit (per second for dates), or `None` if
+    /// `x` is outside the scale or of the wrong type.
+    ///
+    pub fn pdf(&self, x: &Prediction) -> Option<f64> {
+        let normalised = self.normalise_within_scale(x)?;
+        Some(interpolate(&self.pdf, normalised) / self.scale.derivative(normalised))
+    }
+
+    ///
+    /// Returns the probability that the outcome is at most `x`, or `None` if `x` is outside the
+    /// scale or of the wrong type.
+    ///
+    pub fn cdf(&self, x: &Prediction) -> Option<f64> {
+        Some(interpolate(&self.cdf, self.normalise_within_scale(x)?))
+    }
+
+    ///
+    /// Returns the value that the outcome is at most with probability `p`, or `None` if that value
+    /// lies beyond the bounds of the scale (i.e. `p` is less than
+    /// [ContinuousDistribution::mass_below_lower_bound] or greater than one minus
+    /// [ContinuousDistribution::mass_above_upper_bound]).
+    ///
+    pub fn quantile(&self, p: f64) -> Option<Prediction> {
+        if !(self.low..=self.high).contains(&p) {
+            return None;
+        }
+
+        let i = self
+            .cdf
+            .partition_point(|c| *c < p)
+            .clamp(1, self.cdf.len() - 1);
+        let (c0, c1) = (self.cdf[i - 1], self.cdf[i]);
+        let fraction = if c1 > c0 { (p - c0) / (c1 - c0) } else { 0.0 };
+        let normalised = (i as f64 - 1.0 + fraction) / (self.cdf.len() - 1) as f64;
+
+        self.scale.prediction_at(self.scale.denormalise(normalised))
+    }
+
+    /// Returns the median of the distribution, if it lies within the scale.
+    pub fn median(&self) -> Option<Prediction> {
+        self.quantile(0.5)
+    }
+
+    ///
+    /// Returns the mean of the distribution restricted to the scale, since the shape of the
+    /// distribution beyond the bounds is not known. Returns `None` if there is no probability within
+    /// the scale.
+    ///
+    pub fn mean(&self) -> Option<Prediction> {
+        let mass = self.high - self.low;
+        if mass <= 0.0 {
+            return None;
+        }
+
+        let step = 1.0 / (self.pdf.len() - 1) as f64;
+        let weighted: Vec<f64> = self
+            .pdf
+            .iter()
+            .enumerate()
+            .map(|(i, d)| d * self.scale.denormalise(i as f64 * step))
+            .collect();
+        let integral: f64 = weighted
+            .windows(2)
+            .map(|pair| (pair[0] + pair[1]) / 2.0 * step)
+            .sum();
+
+        self.scale.prediction_at(integral / mass)
+    }
+
+    /// Returns the probability that the outcome is below the minimum of the scale.
+    pub fn mass_below_lower_bound(&self) -> f64 {
+        self.low
+    }
+
+    /// Returns the probability that the outcome is above the maximum of the scale.
+    pub fn mass_above_upper_bound(&self) -> f64 {
+        1.0 - self.high
+    }
+
+    /// Returns the density at `x` on the normalised scale, which must be between 0 and 1.
+    pub(crate) fn normalised_pdf(&self, x: f64) -> f64 {
+        interpolate(&self.pdf, x)
+    }
+
+    ///
+    /// Returns the cumulative probability at `x` on the normalised scale, which is constant beyond
+    /// the bounds since the shape of the distribution there is not known.
+    ///
+    pub(crate) fn normalised_cdf(&self, x: f64) -> f64 {
+        interpolate(&self.cdf, x.clamp(0.0, 1.0))
+    }
+
+    fn normalise_within_scale(&self, x: &Prediction) -> Option<f64> {
+        let normalised = self.scale.normalise(self.scale.value_of(x)?);
+        if (0.0..=1.0).contains(&normalised) {
+            Some(normalised)
+        } else {
+            None
+        }
+    }
+}
+
+/// Linearly interpolates between evenly spaced `points` covering the interval from 0 to 1.
+fn interpolate(points: &[f64], x: f64) -> f64 {
+    let position = x * (points.len() - 1) as f64;
+    let i = (position.floor() as usize).min(points.len() - 2);
+    let fraction = position - i as f64;
+    points[i] * (1.0 - fraction) + points[i + 1] * fraction
+}
+
+///
+/// The aggregate community forecast at a single point in a question's history, as recorded in
+/// each entry of its prediction timeseries. Created with [Question::get_timeseries_distribution]
+/// and [Question::get_timeseries_distribution_before].
+///
+/// Values are in real units, as for [ContinuousDistribution]; for binary questions they are
+/// probabilities in [Prediction::NumP].
+///
+/// # Example
+/// ```rust
+/// use metaculustetra::Prediction::NumP;
+/// use std::fs::File;
+/// use std::io::BufReader;
+/// use metaculustetra::Question;
+///
+/// let file = File::open("tests/resolved_range_example.json").unwrap();
+/// let question: Question = serde_json::from_reader(BufReader::new(file)).unwrap();
+/// let distribution = question.get_timeseries_distribution().unwrap();
+///
+/// // 10th and 90th percentiles of the latest community forecast
+/// let lower = distribution.quantile(0.1).unwrap();
+/// let upper = distribution.quantile(0.9).unwrap();
+/// // Probability that GDP growth was negative
+/// let p = distribution.cdf(&NumP(0.0)).unwrap();
+/// assert!(p > 0.05 && p < 0.1);
+///
+/// let mut rng = rand::rng();
+/// let sample = distribution.sample(&mut rng).unwrap();
+/// ```
+///
+/// [Question::get_timeseries_distribution]: crate::Question::get_timeseries_distribution
+/// [Question::get_timeseries_distribution_before]: crate::Question::get_timeseries_distribution_before
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimeseriesDistribution {
+    /// The spread of the individual forecasts on a binary question.
+    Binary(BinaryDistribution),
+    /// The logistic fitted to the community forecast on a continuous question.
+    Logistic(LogisticDistribution),
+}
+
+impl TimeseriesDistribution {
+    ///
+    /// Returns the probability that the outcome is at most `x`, or `None` if `x` is of the wrong
+    /// type for the question.
+    ///
+    pub fn cdf(&self, x: &Prediction) -> Option<f64> {
+        match self {
+            TimeseriesDistribution::Binary(d) => d.cdf(x),
+            TimeseriesDistribution::Logistic(d) => d.cdf(x),
+        }
+    }
+
+    ///
+    /// Returns the value that the outcome is at most with probability `p`, or `None` if `p` is not
+    /// strictly between 0 and 1.
+    ///
+    pub fn quantile(&self, p: f64) -> Option<Prediction> {
+        match self {
+            TimeseriesDistribution::Binary(d) => d.quantile(p),
+            TimeseriesDistribution::Logistic(d) => d.quantile(p),
+        }
+    }
+
+    ///
+    /// Draws a random value from the distribution, which is only `None` if it is a date too far
+    /// from the present to be represented.
+    ///
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Prediction> {
+        match self {
+            TimeseriesDistribution::Binary(d) => d.sample(rng),
+            TimeseriesDistribution::Logistic(d) => d.sample(rng),
+        }
+    }
+}
+
+///
+/// The spread of the individual forecasts on a binary question, which Metaculus summarises by their
+/// mean and variance. It is modelled as the beta distribution with that mean and variance.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BinaryDistribution {
+    avg: f64,
+    var: f64,
+}
+
+impl BinaryDistribution {
+    /// Returns the mean of the individual forecasts.
+    pub fn mean(&self) -> f64 {
+        self.avg
+    }
+
+    /// Returns the variance of the individual forecasts.
+    pub fn variance(&self) -> f64 {
+        self.var
+    }
+
+    ///
+    /// Returns the proportion of forecasts that are at most `x`, or `None` if `x` is not a
+    /// [Prediction::NumP].
+    ///
+    pub fn cdf(&self, x: &Prediction) -> Option<f64> {
+        let x = x.get_if_numeric()?;
+        Some(match self.beta_parameters() {
+            _ if x < 0.0 => 0.0,
+            _ if x >= 1.0 => 1.0,
+            Some((a, b)) => regularised_incomplete_beta(a, b, x),
+            None if x < self.avg => 0.0,
+            None => 1.0,
+        })
+    }
+
+    ///
+    /// Returns the forecast that a proportion `p` of forecasts are at most, or `None` if `p` is not
+    /// strictly between 0 and 1.
+    ///
+    pub fn quantile(&self, p: f64) -> Option<Prediction> {
+        if !(p > 0.0 && p < 1.0) {
+            return None;
+        }
+        let (a, b) = match self.beta_parameters() {
+            Some(parameters) => parameters,
+            None => return Some(NumP(self.avg)),
+        };
+
+        let (mut lower, mut upper) = (0.0, 1.0);
+        for _ in 0..64 {
+            let middle = (lower + upper) / 2.0;
+            if regularised_incomplete_beta(a, b, middle) < p {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        Some(NumP((lower + upper) / 2.0))
+    }
+
+    /// Draws a random forecast from the distribution.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Prediction> {
+        self.quantile(open_unit(rng))
+    }
+
+    ///
+    /// Returns the parameters of the beta distribution with the same mean and variance, or `None`
+    /// if every forecast is the same.
+    ///
+    fn beta_parameters(&self) -> Option<(f64, f64)> {
+        if self.var <= 0.0 || self.avg <= 0.0 || self.avg >= 1.0 {
+            return None;
+        }
+        // The variance of a beta distribution is less than avg * (1 - avg), so keep it just below.
+        let common = (self.avg * (1.0 - self.avg) / self.var - 1.0).max(1e-3);
+        Some((self.avg * common, (1.0 - self.avg) * common))
+    }
+}
+
+///
+/// A logistic distribution fitted by Metaculus to the community forecast on a continuous question,
+/// rescaled so that the probabilities below and above the bounds of the scale match those of the
+/// community forecast. Beyond the bounds, the tails of the logistic are scaled in the same way.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogisticDistribution {
+    component: LogisticComponent,
+    low: f64,
+    high: f64,
+    scale: Scale,
+}
+
+impl LogisticDistribution {
+    pub(crate) fn new(
+        component: LogisticComponent,
+        low: f64,
+        high: f64,
+        scale: Scale,
+    ) -> LogisticDistribution {
+        LogisticDistribution {
+            component,
+            low,
+            high,
+            scale,
+        }
+    }
+
+    /// Returns the fitted logistic on the normalised scale.
+    pub fn component(&self) -> LogisticComponent {
+        self.component
+    }
+
+    /// Returns the probability that the outcome is below the minimum of the scale.
+    pub fn mass_below_lower_bound(&self) -> f64 {
+        self.low
+    }
+
+    /// Returns the probability that the outcome is above the maximum of the scale.
+    pub fn mass_above_upper_bound(&self) -> f64 {
+        1.0 - self.high
+    }
+
+    ///
+    /// Returns the probability that the outcome is at most `x`, or `None` if `x` is of the wrong
+    /// type for the question.
+    ///
+    pub fn cdf(&self, x: &Prediction) -> Option<f64> {
+        Some(self.normalised_cdf(self.scale.normalise(self.scale.value_of(x)?)))
+    }
+
+    ///
+    /// Returns the value that the outcome is at most with probability `p`, or `None` if `p` is not
+    /// strictly between 0 and 1.
+    ///
+    pub fn quantile(&self, p: f64) -> Option<Prediction> {
+        if !(p > 0.0 && p < 1.0) {
+            return None;
+        }
+        let c = &self.component;
+        let (at_min, at_max) = (c.cdf(0.0), c.cdf(1.0));
+
+        // A logistic too narrow to reach a bound in floating point has no shape beyond it, so the
+        // outcome is placed at the bound.
+        let normalised = if p < self.low {
+            if at_min > 0.0 {
+                c.quantile(p / self.low * at_min)
+            } else {
+                0.0
+            }
+        } else if p > self.high {
+            if at_max < 1.0 {
+                c.quantile(1.0 - (1.0 - p) / (1.0 - self.high) * (1.0 - at_max))
+            } else {
+                1.0
+            }
+        } else if self.high > self.low && at_max > at_min {
+            c.quantile(at_min + (p - self.low) / (self.high - self.low) * (at_max - at_min))
+        } else if self.high > self.low {
+            (p - self.low) / (self.high - self.low)
+        } else {
+            0.0
+        };
+        self.scale.prediction_at(self.scale.denormalise(normalised))
+    }
+
+    /// Draws a random outcome from the distribution.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Prediction> {
+        self.quantile(open_unit(rng))
+    }
+
+    /// Returns the cumulative probability at `x` on the normalised scale.
+    pub(crate) fn normalised_cdf(&self, x: f64) -> f64 {
+        let c = &self.component;
+        let (at_min, at_max) = (c.cdf(0.0), c.cdf(1.0));
+
+        // The denominators are zero for a logistic too narrow to reach a bound in floating point,
+        // which puts all of the probability beyond that bound at the bound itself.
+        if x < 0.0 {
+            if at_min > 0.0 {
+                self.low * c.cdf(x) / at_min
+            } else {
+                self.low
+            }
+        } else if x > 1.0 {
+            if at_max < 1.0 {
+                1.0 - (1.0 - self.high) * (1.0 - c.cdf(x)) / (1.0 - at_max)
+            } else {
+                self.high
+            }
+        } else if at_max > at_min {
+            self.low + (self.high - self.low) * (c.cdf(x) - at_min) / (at_max - at_min)
+        } else {
+            self.low + (self.high - self.low) * x
+        }
+    }
+
+    /// Returns the density at `x` on the normalised scale, which must be between 0 and 1.
+    pub(crate) fn normalised_pdf(&self, x: f64) -> f64 {
+        let c = &self.component;
+        let within = c.cdf(1.0) - c.cdf(0.0);
+        if within > 0.0 {
+            (self.high - self.low) * c.pdf(x) / within
+        } else {
+            self.high - self.low
+        }
+    }
+}
+
+///
+/// The `distribution` recorded in each entry of a prediction timeseries, which is only converted
+/// into a [TimeseriesDistribution] once the question's scale is known.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub(crate) enum TimeseriesDistributionRepr {
+    Logistic {
+        x0: f64,
+        s: f64,
+        low: f64,
+        high: f64,
+    },
+    Binary {
+        avg: f64,
+        var: f64,
+    },
+}
+
+impl TimeseriesDistributionRepr {
+    pub(crate) fn with_scale(&self, scale: Option<Scale>) -> Option<TimeseriesDistribution> {
+        match *self {
+            TimeseriesDistributionRepr::Logistic { x0, s, low, high } if s > 0.0 => {
+                Some(TimeseriesDistribution::Logistic(LogisticDistribution {
+                    component: LogisticComponent { x0, s, w: 1.0 },
+                    low,
+                    high,
+                    scale: scale?,
+                }))
+            }
+            TimeseriesDistributionRepr::Logistic { .. } => None,
+            TimeseriesDistributionRepr::Binary { avg, var } => {
+                Some(TimeseriesDistribution::Binary(BinaryDistribution {
+                    avg,
+                    var,
+                }))
+            }
+        }
+    }
+}
+
+/// Draws a uniformly distributed number strictly between 0 and 1.
+fn open_unit<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    loop {
+        let u: f64 = rng.random();
+        if u > 0.0 {
+            return u;
+        }
+    }
+}
+
+///
+/// Returns the regularised incomplete beta function I_x(a, b), the cumulative distribution function
+/// of the beta distribution, evaluated with a continued fraction (Numerical Recipes, section 6.4).
+///
+fn regularised_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..300 {
+        let m = m as f64;
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-14 {
+            break;
+        }
+    }
+    h
+}
+
+/// Returns the natural logarithm of the gamma function, using the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let mut series = 1.000000000190015;
+    for (i, coefficient) in COEFFICIENTS.iter().enumerate() {
+        series += coefficient / (x + 1.0 + i as f64);
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
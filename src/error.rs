@@ -0,0 +1,120 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+///
+/// The reasons a request to Metaculus can fail.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaculusError {
+    /// The request could not be sent, or no response was received (e.g. DNS or TLS failures).
+    Transport(String),
+    /// Metaculus responded with an unsuccessful HTTP status code.
+    Status {
+        /// The HTTP status code of the response, such as `429` or `503`.
+        status: u16,
+        /// The body of the response, which usually explains what went wrong.
+        body: String,
+    },
+    /// The response could not be deserialized into the expected type.
+    Deserialize {
+        /// The path to the field that failed to deserialize, such as `possibilities.scale`.
+        path: String,
+        /// The error reported by the deserializer.
+        message: String,
+    },
+    ///
+    /// Metaculus rejected the request's credentials, either because the API token is invalid or
+    /// because it does not grant access to the requested resource.
+    ///
+    Unauthorized {
+        /// The HTTP status code of the response, either `401` or `403`.
+        status: u16,
+        /// The body of the response, which usually explains why the token was rejected.
+        body: String,
+    },
+    ///
+    /// A forecast was not submitted because it is not valid for the question, such as a
+    /// probability on a continuous question or a distribution centred outside the question's scale.
+    ///
+    InvalidForecast(String),
+    ///
+    /// An index could not be built from the given components, such as when the number of question
+    /// ids does not match the number of weights.
+    ///
+    InvalidIndex(String),
+    ///
+    /// A Metaculus domain could not be used, either because it is not a single DNS label such as
+    /// `pandemic` or because the client's base URL has no subdomain to replace with it.
+    ///
+    InvalidDomain(String),
+    /// The requested resource (such as a question with a given id) does not exist.
+    NotFound {
+        /// The id of the resource that was requested.
+        id: String,
+    },
+}
+
+impl Display for MetaculusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetaculusError::Transport(message) => write!(f, "transport error: {}", message),
+            MetaculusError::Status { status, body } => {
+                write!(f, "HTTP status {}: {}", status, body)
+            }
+            MetaculusError::Deserialize { path, message } => {
+                write!(f, "failed to deserialize `{}`: {}", path, message)
+            }
+            MetaculusError::Unauthorized { status, body } => {
+                write!(f, "unauthorized (HTTP status {}): {}", status, body)
+            }
+            MetaculusError::InvalidForecast(reason) => write!(f, "invalid forecast: {}", reason),
+            MetaculusError::InvalidIndex(reason) => write!(f, "invalid index: {}", reason),
+            MetaculusError::InvalidDomain(reason) => write!(f, "invalid domain: {}", reason),
+            MetaculusError::NotFound { id } => write!(f, "{} not found", id),
+        }
+    }
+}
+
+impl std::error::Error for MetaculusError {}
+
+///
+/// Deserializes a JSON response body, recording the path to the field that failed if it does not
+/// match the expected type.
+///
+pub(crate) fn from_json<T: DeserializeOwned>(body: &str) -> Result<T, MetaculusError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(body);
+    serde_path_to_error::deserialize(deserializer).map_err(deserialize_error)
+}
+
+/// Deserializes an already parsed JSON value, recording the path to the field that failed as in
+/// [from_json].
+pub(crate) fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, MetaculusError> {
+    serde_path_to_error::deserialize(value).map_err(deserialize_error)
+}
+
+///
+/// Converts an error from a deserializer wrapped with `serde_path_to_error` into a
+/// [MetaculusError::Deserialize].
+///
+pub(crate) fn deserialize_error<E: Display>(e: serde_path_to_error::Error<E>) -> MetaculusError {
+    MetaculusError::Deserialize {
+        path: e.path().to_string(),
+        message: e.inner().to_string(),
+    }
+}
+
+///
+/// Deserializes a field that Metaculus may record in shapes this crate does not know, treating
+/// anything that does not match `T` as its default (such as `None` or an empty string) instead of
+/// failing the whole response. Used with `#[serde(default, deserialize_with = "lenient")]`.
+///
+pub(crate) fn lenient<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned + Default,
+{
+    let value = Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).unwrap_or_default())
+}
@@ -0,0 +1,122 @@
+//!
+//! A module for individual users' forecasts on questions, both those retrieved from Metaculus and
+//! those to be submitted.
+//!
+
+use crate::distribution::LogisticMixture;
+use crate::{MetaculusError, Question};
+use chrono::NaiveDateTime;
+use serde_json::json;
+
+///
+/// A single user's forecast on a question.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum Forecast {
+    /// A probability (from 0.0 to 1.0) on a binary question.
+    Probability(f64),
+    /// A distribution on a continuous (numeric or date) question.
+    Continuous(LogisticMixture),
+}
+
+///
+/// A forecast made by the authenticated user, along with the time it was made.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserPrediction {
+    /// The time the forecast was made.
+    pub time: NaiveDateTime,
+    /// The forecast itself.
+    pub forecast: Forecast,
+}
+
+/// The most components Metaculus accepts in a continuous forecast.
+pub const MAX_COMPONENTS: usize = 5;
+
+impl Forecast {
+    ///
+    /// Checks that the forecast can be submitted to `question`, returning a
+    /// [MetaculusError::InvalidForecast] explaining the problem if not. Probabilities must be on a
+    /// binary question and between 0.01 and 0.99. Distributions must be on a continuous question,
+    /// have between 1 and [MAX_COMPONENTS] components with positive weights and scales, and have
+    /// every component centred within the question's scale (between `0.0` and `1.0` on the
+    /// normalised scale, see [Question::normalise]).
+    ///
+    pub fn validate_for(&self, question: &Question) -> Result<(), MetaculusError> {
+        let invalid = |reason: String| Err(MetaculusError::InvalidForecast(reason));
+
+        match self {
+            Forecast::Probability(p) => {
+                if !question.is_binary() {
+                    return invalid(
+                        "a probability can only be submitted on a binary question".into(),
+                    );
+                }
+                if !(0.01..=0.99).contains(p) {
+                    return invalid(format!("probability {} is not between 0.01 and 0.99", p));
+                }
+            }
+            Forecast::Continuous(mixture) => {
+                if question.possibilities.scale.is_none() {
+                    return invalid(
+                        "a distribution can only be submitted on a continuous question".into(),
+                    );
+                }
+                if mixture.components.is_empty() || mixture.components.len() > MAX_COMPONENTS {
+                    return invalid(format!(
+                        "a distribution must have between 1 and {} components, not {}",
+                        MAX_COMPONENTS,
+                        mixture.components.len()
+                    ));
+                }
+                for c in &mixture.components {
+                    if !(0.0..=1.0).contains(&c.x0) {
+                        return invalid(format!(
+                            "component centre {} is outside the question's scale",
+                            c.x0
+                        ));
+                    }
+                    if !(c.s > 0.0 && c.s.is_finite()) {
+                        return invalid(format!("component scale {} is not positive", c.s));
+                    }
+                    if !(c.w > 0.0 && c.w.is_finite()) {
+                        return invalid(format!("component weight {} is not positive", c.w));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the body of the request that submits this forecast to `question`. Component weights
+    /// are normalised to sum to one, and the probability each component assigns below and above the
+    /// scale is zero unless that bound of the question is open.
+    ///
+    pub(crate) fn to_submission(&self, question: &Question) -> serde_json::Value {
+        let prediction = match self {
+            Forecast::Probability(p) => json!(p),
+            Forecast::Continuous(mixture) => {
+                let total_weight = mixture.total_weight();
+                let components: Vec<serde_json::Value> = mixture
+                    .components
+                    .iter()
+                    .map(|c| {
+                        json!({
+                            "kind": "logistic",
+                            "x0": c.x0,
+                            "s": c.s,
+                            "w": c.w / total_weight,
+                            "low": if question.has_open_lower_bound() { c.cdf(0.0) } else { 0.0 },
+                            "high": if question.has_open_upper_bound() { c.cdf(1.0) } else { 1.0 },
+                        })
+                    })
+                    .collect();
+                json!({ "kind": "multi", "d": components })
+            }
+        };
+
+        json!({ "prediction": prediction, "void": false })
+    }
+}
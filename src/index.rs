@@ -4,10 +4,27 @@
 //! [here](https://www.metaculus.com/questions/935/platform-feature-suggestions/#comment-69686).
 //!
 
+mod breakdown;
+mod definition;
+mod normalisation;
+mod uncertainty;
+
+pub use self::breakdown::{ComponentChange, Contribution, IndexDiff};
+pub use self::definition::{IndexDefinition, QuestionDefinition};
+pub use self::normalisation::Normalisation;
+pub use self::uncertainty::IndexUncertainty;
 use crate::date_utils::DateUtils;
+use crate::timeseries::{self, Sample};
+#[cfg(feature = "async")]
+use crate::AsyncMetaculus;
 use crate::RangeQuestionScale::{DateRangeQuestionScale, NumericRangeQuestionScale};
-use crate::{AmbP, DatP, Metaculus, NumP, Question};
-use chrono::NaiveDateTime;
+use crate::{
+    AboveUpper, AmbP, Annulled, BelowLower, DatP, Metaculus, MetaculusError, NumP, Prediction,
+    Question,
+};
+use chrono::{Duration, NaiveDateTime};
+#[cfg(feature = "async")]
+use futures::stream::{self, StreamExt};
 
 ///
 /// An index, containing a list of weighted questions.
@@ -57,6 +74,24 @@ impl Index {
             .map(|q| q.get_value_before(date))
             .sum::<f64>()
     }
+
+    ///
+    /// Samples the value of the index at regular `interval`s from `start` to `end` (inclusive), as
+    /// given by [Index::get_index_value_before].
+    ///
+    pub fn resample(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        interval: Duration,
+    ) -> Vec<Sample<f64>> {
+        timeseries::grid(start, end, interval)
+            .map(|time| Sample {
+                time,
+                value: self.get_index_value_before(time),
+            })
+            .collect()
+    }
 }
 
 impl WeightedQuestion {
@@ -112,6 +147,17 @@ impl WeightedQuestion {
         })
     }
 
+    ///
+    /// Makes a new `WeightedQuestion` from a [Question] of any supported type, trying
+    /// [WeightedQuestion::create_from_binary], then [WeightedQuestion::create_from_range], then
+    /// [WeightedQuestion::create_from_date].
+    ///
+    fn create_from_any(question: &Question, weight: f64) -> Option<WeightedQuestion> {
+        WeightedQuestion::create_from_binary(question, weight)
+            .or_else(|| WeightedQuestion::create_from_range(question, weight))
+            .or_else(|| WeightedQuestion::create_from_date(question, weight))
+    }
+
     ///
     /// Get the value that the current prediction on this weighted question alone contributes to its
     /// index.
@@ -125,57 +171,283 @@ impl WeightedQuestion {
     /// to its index.
     ///
     pub fn get_value_before(&self, date: NaiveDateTime) -> f64 {
-        if self.question.get_resolution() == Some(AmbP) {
-            return 0.0;
+        self.get_contribution_before(date).contribution
+    }
+
+    /// Returns `true` if the question resolved ambiguously or was annulled, so it is left out.
+    fn is_void(&self) -> bool {
+        matches!(self.question.get_resolution(), Some(AmbP | Annulled))
+    }
+
+    ///
+    /// Converts a `prediction` on this question into the units of the index, before weighting, or
+    /// returns `None` if it does not count towards the index.
+    ///
+    fn transform(&self, prediction: &Prediction) -> Option<f64> {
+        let value = match prediction {
+            NumP(p) => *p,
+            DatP(p) => p.and_utc().timestamp() as f64,
+            // Resolutions beyond an open bound count as resolving at that bound.
+            BelowLower | AboveUpper => self.question.scale()?.value_of(prediction)?,
+            _ => return None,
+        };
+
+        if self.linearise_if_log && self.question.is_logarithmic() {
+            Some((value / self.zero).ln())
+        } else {
+            Some(value - self.zero)
         }
+    }
+}
 
-        match self.question.get_best_prediction_before(date) {
-            None => 0.0,
-            Some(p) => match p {
-                NumP(p) => {
-                    if self.linearise_if_log && self.question.is_logarithmic() {
-                        (p / self.zero).ln() * self.weight
-                    } else {
-                        (p - self.zero) * self.weight
-                    }
-                }
-                DatP(p) => {
-                    if self.linearise_if_log && self.question.is_logarithmic() {
-                        (p.timestamp() as f64 / self.zero).ln() * self.weight
-                    } else {
-                        (p.timestamp() as f64 - self.zero) * self.weight
+///
+/// The result of building an [Index] from question ids: the index itself, and the questions that
+/// were left out of it and why.
+///
+pub struct IndexBuildReport {
+    /// The index, made up of every question that could be fetched and weighted.
+    pub index: Index,
+    /// The questions that were left out of the index, in the order they were given.
+    pub skipped: Vec<SkippedQuestion>,
+}
+
+/// A question that was left out of an index when it was built.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkippedQuestion {
+    /// The id of the question.
+    pub id: String,
+    /// Why the question was left out.
+    pub reason: SkipReason,
+}
+
+/// The reasons a question can be left out of an index.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SkipReason {
+    /// The question could not be retrieved.
+    Fetch(MetaculusError),
+    ///
+    /// The question is not a binary, numeric or date question (such as a group or a discussion),
+    /// so it cannot be weighted.
+    ///
+    UnsupportedType,
+}
+
+impl IndexBuildReport {
+    /// Returns `true` iff every question made it into the index.
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty()
+    }
+
+    ///
+    /// Builds an index from `(id, question, spec)` triples, weighting each fetched question with
+    /// `weigh` and recording why any were left out.
+    ///
+    pub(crate) fn build<T>(
+        components: Vec<(String, Result<Question, MetaculusError>, T)>,
+        weigh: impl Fn(&Question, T) -> Option<WeightedQuestion>,
+    ) -> IndexBuildReport {
+        let mut questions = Vec::new();
+        let mut skipped = Vec::new();
+        for (id, question, spec) in components {
+            let reason = match question {
+                Ok(question) => match weigh(&question, spec) {
+                    Some(weighted) => {
+                        questions.push(weighted);
+                        continue;
                     }
-                }
-                _ => 0.0,
-            },
+                    None => SkipReason::UnsupportedType,
+                },
+                Err(e) => SkipReason::Fetch(e),
+            };
+            skipped.push(SkippedQuestion { id, reason });
+        }
+
+        IndexBuildReport {
+            index: Index { questions },
+            skipped,
         }
     }
 }
 
+/// Checks that there is one weight for each question id.
+fn check_lengths(ids: &[String], weights: &[f64]) -> Result<(), MetaculusError> {
+    if ids.len() == weights.len() {
+        Ok(())
+    } else {
+        Err(MetaculusError::InvalidIndex(format!(
+            "{} question ids were given, but {} weights",
+            ids.len(),
+            weights.len()
+        )))
+    }
+}
+
 pub trait MetaculusIndexCreator {
+    #[deprecated(
+        note = "use `Metaculus::try_create_index_from_questions`, which reports the questions it \
+                leaves out"
+    )]
     fn create_index_from_questions(&self, ids: Vec<String>, weights: Vec<f64>) -> Index;
 }
 
-impl MetaculusIndexCreator for Metaculus<'_> {
+impl MetaculusIndexCreator for Metaculus {
     ///
     /// Creates an [Index] from a list of question `ids`, each of which have the given weight,
-    /// ignoring questions which cannot be received or parsed successfully.
+    /// ignoring questions which cannot be received or parsed successfully. If there are more ids
+    /// than weights or the other way round, the extra ones are ignored.
+    ///
+    /// Deprecated in favour of [Metaculus::try_create_index_from_questions], which rejects
+    /// mismatched lengths and reports what was ignored and why.
     ///
     fn create_index_from_questions(&self, ids: Vec<String>, weights: Vec<f64>) -> Index {
-        let questions = ids
+        self.build_index_from_questions(ids, weights).index
+    }
+}
+
+impl Metaculus {
+    ///
+    /// Creates an [Index] from a list of question `ids`, each of which have the given weight,
+    /// reporting which questions were left out because they could not be retrieved or weighted.
+    /// Returns a [MetaculusError::InvalidIndex] if there is not exactly one weight for each id.
+    ///
+    pub fn try_create_index_from_questions(
+        &self,
+        ids: Vec<String>,
+        weights: Vec<f64>,
+    ) -> Result<IndexBuildReport, MetaculusError> {
+        check_lengths(&ids, &weights)?;
+        Ok(self.build_index_from_questions(ids, weights))
+    }
+
+    /// Builds the index for each id that has a weight, ignoring any extra ids or weights.
+    fn build_index_from_questions(&self, ids: Vec<String>, weights: Vec<f64>) -> IndexBuildReport {
+        let components = ids
+            .into_iter()
+            .zip(weights)
+            .map(|(id, weight)| {
+                let question = self.try_get_question(&id);
+                (id, question, weight)
+            })
+            .collect();
+
+        IndexBuildReport::build(components, WeightedQuestion::create_from_any)
+    }
+
+    ///
+    /// Creates an [Index] from an [IndexDefinition], fetching its questions from the definition's
+    /// domain (or this client's, if it has none) and reporting which questions were left out
+    /// because they could not be retrieved or weighted. Returns a [MetaculusError::InvalidDomain]
+    /// if the definition's domain cannot be used with this client.
+    ///
+    pub fn create_index_from_definition(
+        &self,
+        definition: &IndexDefinition,
+    ) -> Result<IndexBuildReport, MetaculusError> {
+        let metaculus = match &definition.domain {
+            Some(domain) => self.on_domain(domain)?,
+            None => self.clone(),
+        };
+        let questions = definition
+            .questions
             .iter()
-            .map(|id| self.get_question(id))
+            .map(|q| metaculus.try_get_question(&q.id.to_string()))
+            .collect();
+
+        Ok(definition.materialise(questions))
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncMetaculus {
+    ///
+    /// Creates an [Index] from a list of question `ids`, each of which have the given weight,
+    /// ignoring questions which cannot be received or parsed successfully. At most `concurrency`
+    /// questions are fetched at once. If there are more ids than weights or the other way round,
+    /// the extra ones are ignored.
+    ///
+    #[deprecated(
+        note = "use `AsyncMetaculus::try_create_index_from_questions`, which reports the questions \
+                it leaves out"
+    )]
+    pub async fn create_index_from_questions(
+        &self,
+        ids: Vec<String>,
+        weights: Vec<f64>,
+        concurrency: usize,
+    ) -> Index {
+        self.build_index_from_questions(ids, weights, concurrency)
+            .await
+            .index
+    }
+
+    ///
+    /// Creates an [Index] from a list of question `ids`, each of which have the given weight,
+    /// reporting which questions were left out because they could not be retrieved or weighted.
+    /// At most `concurrency` questions are fetched at once. Returns a
+    /// [MetaculusError::InvalidIndex] if there is not exactly one weight for each id.
+    ///
+    pub async fn try_create_index_from_questions(
+        &self,
+        ids: Vec<String>,
+        weights: Vec<f64>,
+        concurrency: usize,
+    ) -> Result<IndexBuildReport, MetaculusError> {
+        check_lengths(&ids, &weights)?;
+        Ok(self
+            .build_index_from_questions(ids, weights, concurrency)
+            .await)
+    }
+
+    /// Builds the index for each id that has a weight, ignoring any extra ids or weights.
+    async fn build_index_from_questions(
+        &self,
+        mut ids: Vec<String>,
+        weights: Vec<f64>,
+        concurrency: usize,
+    ) -> IndexBuildReport {
+        ids.truncate(weights.len());
+        let questions: Vec<Result<Question, MetaculusError>> = stream::iter(ids.iter())
+            .map(|id| self.try_get_question(id))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let components = ids
+            .into_iter()
+            .zip(questions)
             .zip(weights)
-            .filter_map(|pair| {
-                let q = pair.0?;
-                let weight = pair.1;
+            .map(|((id, question), weight)| (id, question, weight))
+            .collect();
+        IndexBuildReport::build(components, WeightedQuestion::create_from_any)
+    }
 
-                WeightedQuestion::create_from_binary(&q, weight)
-                    .or_else(|| WeightedQuestion::create_from_range(&q, weight))
-                    .or_else(|| WeightedQuestion::create_from_date(&q, weight))
-            })
+    ///
+    /// Creates an [Index] from an [IndexDefinition], fetching its questions from the definition's
+    /// domain (or this client's, if it has none) and reporting which questions were left out
+    /// because they could not be retrieved or weighted. At most `concurrency` questions are
+    /// fetched at once. Returns a [MetaculusError::InvalidDomain] if the definition's domain cannot
+    /// be used with this client.
+    ///
+    pub async fn create_index_from_definition(
+        &self,
+        definition: &IndexDefinition,
+        concurrency: usize,
+    ) -> Result<IndexBuildReport, MetaculusError> {
+        let metaculus = match &definition.domain {
+            Some(domain) => self.on_domain(domain)?,
+            None => self.clone(),
+        };
+        let ids: Vec<String> = definition
+            .questions
+            .iter()
+            .map(|q| q.id.to_string())
             .collect();
+        let questions = stream::iter(ids.iter())
+            .map(|id| metaculus.try_get_question(id))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
 
-        Index { questions }
+        Ok(definition.materialise(questions))
     }
 }
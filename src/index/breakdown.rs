@@ -0,0 +1,127 @@
+use crate::date_utils::DateUtils;
+use crate::index::{Index, WeightedQuestion};
+use crate::Prediction;
+use chrono::NaiveDateTime;
+
+///
+/// What a single [WeightedQuestion] contributes to the value of its [Index] at some date, as
+/// returned by [Index::breakdown_before].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contribution {
+    /// The id of the question.
+    pub id: u64,
+    /// The title of the question.
+    pub title: String,
+    /// The best prediction on the question at the date, or its resolution once it has resolved.
+    pub prediction: Option<Prediction>,
+    ///
+    /// The prediction in the units of the index, after subtracting the zero point (or taking the
+    /// logarithm) but before weighting. This is `None` if the question does not count towards the
+    /// index, such as when it has no prediction yet or was annulled.
+    ///
+    pub value: Option<f64>,
+    /// The weight of the question in the index.
+    pub weight: f64,
+    /// The amount added to the index by the question: the weighted value, or `0.0` if there is none.
+    pub contribution: f64,
+}
+
+///
+/// The change in the value of an [Index] between two dates, attributed to each of its questions,
+/// as returned by [Index::diff].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexDiff {
+    /// The value of the index at the earlier date.
+    pub from_value: f64,
+    /// The value of the index at the later date.
+    pub to_value: f64,
+    /// The change in the value of the index, `to_value - from_value`.
+    pub change: f64,
+    /// The change in each question, in the same order as [Index::questions].
+    pub components: Vec<ComponentChange>,
+}
+
+/// The change in what a single question contributes to its [Index] between two dates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentChange {
+    /// The contribution at the earlier date.
+    pub before: Contribution,
+    /// The contribution at the later date.
+    pub after: Contribution,
+    /// The change in the contribution, which is this question's share of [IndexDiff::change].
+    pub change: f64,
+}
+
+impl Index {
+    /// Breaks the current value of the index down into what each question contributes to it.
+    pub fn breakdown(&self) -> Vec<Contribution> {
+        self.breakdown_before(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Breaks the value of the index on the given `date` down into what each question contributes
+    /// to it, in the same order as [Index::questions]. The contributions sum to
+    /// [Index::get_index_value_before].
+    ///
+    pub fn breakdown_before(&self, date: NaiveDateTime) -> Vec<Contribution> {
+        self.questions
+            .iter()
+            .map(|q| q.get_contribution_before(date))
+            .collect()
+    }
+
+    ///
+    /// Compares the value of the index on the dates `from` and `to`, attributing the change to each
+    /// of its questions.
+    ///
+    pub fn diff(&self, from: NaiveDateTime, to: NaiveDateTime) -> IndexDiff {
+        let components: Vec<ComponentChange> = self
+            .breakdown_before(from)
+            .into_iter()
+            .zip(self.breakdown_before(to))
+            .map(|(before, after)| ComponentChange {
+                change: after.contribution - before.contribution,
+                before,
+                after,
+            })
+            .collect();
+
+        let from_value = components
+            .iter()
+            .map(|c| c.before.contribution)
+            .sum::<f64>();
+        let to_value = components.iter().map(|c| c.after.contribution).sum::<f64>();
+        IndexDiff {
+            from_value,
+            to_value,
+            change: to_value - from_value,
+            components,
+        }
+    }
+}
+
+impl WeightedQuestion {
+    ///
+    /// Get what the prediction on this weighted question at the given `date` contributes to its
+    /// index, along with how it was worked out.
+    ///
+    pub fn get_contribution_before(&self, date: NaiveDateTime) -> Contribution {
+        let prediction = self.question.get_best_prediction_before(date);
+        let value = if self.is_void() {
+            None
+        } else {
+            prediction.as_ref().and_then(|p| self.transform(p))
+        };
+
+        Contribution {
+            id: self.question.id,
+            title: self.question.title.clone(),
+            prediction,
+            value,
+            weight: self.weight,
+            contribution: value.map_or(0.0, |value| value * self.weight),
+        }
+    }
+}
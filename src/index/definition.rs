@@ -0,0 +1,126 @@
+#[cfg(any(feature = "toml", feature = "yaml"))]
+use crate::error::deserialize_error;
+use crate::index::{IndexBuildReport, WeightedQuestion};
+use crate::{MetaculusError, Question};
+use serde::{Deserialize, Serialize};
+
+///
+/// A description of an [Index](super::Index) that can be kept in a file, listing each question by
+/// id instead of holding its data. It is turned into an index by fetching the questions with
+/// [Metaculus::create_index_from_definition](crate::Metaculus::create_index_from_definition).
+///
+/// Definitions can be read from JSON, from TOML with the `toml` feature, and from YAML with the
+/// `yaml` feature, and (de)serialized with any other serde format.
+///
+/// # Example
+///
+/// ``` rust
+/// use metaculustetra::index::IndexDefinition;
+///
+/// let definition = IndexDefinition::from_json(r#"{
+///     "name": "AI progress",
+///     "description": "How quickly AI is advancing",
+///     "questions": [
+///         {"id": 3479, "weight": 1.0},
+///         {"id": 7887, "weight": 0.5, "zero": 0.0, "linearise_if_log": false}
+///     ]
+/// }"#).unwrap();
+/// assert_eq!(definition.questions.len(), 2);
+/// assert_eq!(definition.domain, None);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IndexDefinition {
+    /// The name of the index.
+    pub name: String,
+    /// What the index measures.
+    #[serde(default)]
+    pub description: String,
+    ///
+    /// The Metaculus domain the questions are on, such as `www` or `pandemic`, or `None` to use the
+    /// domain of the client the index is created with. This replaces the subdomain of the client's
+    /// base URL, so it cannot be used with a client whose base URL has no subdomain, such as a
+    /// local proxy.
+    ///
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// The questions making up the index.
+    pub questions: Vec<QuestionDefinition>,
+}
+
+///
+/// A question in an [IndexDefinition], and how it is weighted. The zero point and linearisation
+/// default to those chosen by [WeightedQuestion::create_from_binary],
+/// [WeightedQuestion::create_from_range] or [WeightedQuestion::create_from_date], whichever suits
+/// the question.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct QuestionDefinition {
+    /// The id of the question.
+    pub id: u64,
+    /// The weight of the question in the index.
+    pub weight: f64,
+    /// Overrides the zero point of the question (see [WeightedQuestion::zero]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zero: Option<f64>,
+    ///
+    /// Overrides whether predictions on a logarithmic scale are linearised (see
+    /// [WeightedQuestion::linearise_if_log]).
+    ///
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linearise_if_log: Option<bool>,
+}
+
+impl IndexDefinition {
+    /// Reads a definition from JSON.
+    pub fn from_json(definition: &str) -> Result<IndexDefinition, MetaculusError> {
+        crate::error::from_json(definition)
+    }
+
+    /// Reads a definition from TOML. Requires the `toml` feature.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(definition: &str) -> Result<IndexDefinition, MetaculusError> {
+        let deserializer = toml::Deserializer::new(definition);
+        serde_path_to_error::deserialize(deserializer).map_err(deserialize_error)
+    }
+
+    /// Reads a definition from YAML. Requires the `yaml` feature.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(definition: &str) -> Result<IndexDefinition, MetaculusError> {
+        let deserializer = serde_yaml::Deserializer::from_str(definition);
+        serde_path_to_error::deserialize(deserializer).map_err(deserialize_error)
+    }
+
+    ///
+    /// Makes an index from the `questions` fetched for this definition, in the same order as
+    /// [IndexDefinition::questions], reporting any that could not be fetched or weighted.
+    ///
+    pub(crate) fn materialise(
+        &self,
+        questions: Vec<Result<Question, MetaculusError>>,
+    ) -> IndexBuildReport {
+        let components = self
+            .questions
+            .iter()
+            .zip(questions)
+            .map(|(definition, question)| (definition.id.to_string(), question, definition))
+            .collect();
+
+        IndexBuildReport::build(components, |question, definition| {
+            definition.weigh(question)
+        })
+    }
+}
+
+impl QuestionDefinition {
+    /// Weights the `question` as described by this definition, if it is of a supported type.
+    fn weigh(&self, question: &Question) -> Option<WeightedQuestion> {
+        let mut weighted = WeightedQuestion::create_from_any(question, self.weight)?;
+        if let Some(zero) = self.zero {
+            weighted.zero = zero;
+        }
+        if let Some(linearise_if_log) = self.linearise_if_log {
+            weighted.linearise_if_log = linearise_if_log;
+        }
+        Some(weighted)
+    }
+}
@@ -0,0 +1,173 @@
+use crate::date_utils::DateUtils;
+use crate::index::{Index, WeightedQuestion};
+use crate::timeseries::TimeseriesPoint;
+use crate::NumP;
+use chrono::NaiveDateTime;
+
+///
+/// How each question is put into common units before it is weighted, so that an [Index] can mix
+/// probabilities, quantities, dates and logarithmic questions without hand-tuned weights.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Normalisation {
+    ///
+    /// The units used by [Index::get_index_value]: probabilities for binary questions, and the
+    /// distance from [WeightedQuestion::zero] (or its logarithm) for continuous questions, in
+    /// seconds for dates.
+    ///
+    #[default]
+    Raw,
+    ///
+    /// The position of the prediction within the range of the question, from `0.0` at the minimum
+    /// to `1.0` at the maximum. Logarithmic questions are measured along their logarithmic scale,
+    /// and probabilities are left as they are.
+    ///
+    ScaleRange,
+    ///
+    /// The number of standard deviations the prediction is from the mean of the question's own
+    /// prediction history up to that date (or its close, if earlier), using the Metaculus
+    /// prediction if the question has one and the community prediction otherwise, as
+    /// [Question::get_best_prediction_before] does. Questions with fewer than two points of
+    /// history, or whose prediction has never moved, count as `0.0`.
+    ///
+    /// [Question::get_best_prediction_before]: crate::Question::get_best_prediction_before
+    ///
+    ZScore,
+}
+
+impl Index {
+    ///
+    /// Get the current value of the index with every question put into common units by
+    /// `normalisation` before it is weighted.
+    ///
+    pub fn get_normalised_value(&self, normalisation: Normalisation) -> f64 {
+        self.get_normalised_value_before(NaiveDateTime::latest_prediction_date(), normalisation)
+    }
+
+    ///
+    /// Get the value of the index as it was on the given `date`, with every question put into
+    /// common units by `normalisation` before it is weighted. [Normalisation::Raw] gives the same
+    /// value as [Index::get_index_value_before].
+    ///
+    pub fn get_normalised_value_before(
+        &self,
+        date: NaiveDateTime,
+        normalisation: Normalisation,
+    ) -> f64 {
+        self.questions
+            .iter()
+            .filter_map(|q| Some(q.get_normalised_value_before(date, normalisation)? * q.weight))
+            .sum::<f64>()
+    }
+
+    ///
+    /// Get the current value of the index on a scale from 0 to 100, as described in
+    /// [Index::get_percentage_before].
+    ///
+    pub fn get_percentage(&self) -> Option<f64> {
+        self.get_percentage_before(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Get the value of the index on the given `date` on a scale from 0 to 100: the weighted mean
+    /// of each question's position in its range ([Normalisation::ScaleRange]), as a percentage. A
+    /// question with a negative weight counts by how far its prediction is from the maximum instead
+    /// of the minimum.
+    ///
+    /// Questions that do not count towards the index, such as those without a prediction yet, are
+    /// left out of the mean. This is `None` if no question counts.
+    ///
+    pub fn get_percentage_before(&self, date: NaiveDateTime) -> Option<f64> {
+        let (total, weights) = self
+            .questions
+            .iter()
+            .filter_map(|q| {
+                let position = q.get_normalised_value_before(date, Normalisation::ScaleRange)?;
+                if q.weight < 0.0 {
+                    Some(((1.0 - position) * -q.weight, -q.weight))
+                } else {
+                    Some((position * q.weight, q.weight))
+                }
+            })
+            .fold((0.0, 0.0), |(total, weights), (value, weight)| {
+                (total + value, weights + weight)
+            });
+
+        if weights > 0.0 {
+            Some(total / weights * 100.0)
+        } else {
+            None
+        }
+    }
+}
+
+impl WeightedQuestion {
+    ///
+    /// Get the prediction on this weighted question at the given `date` in the units given by
+    /// `normalisation`, before weighting, or `None` if it does not count towards the index.
+    ///
+    pub fn get_normalised_value_before(
+        &self,
+        date: NaiveDateTime,
+        normalisation: Normalisation,
+    ) -> Option<f64> {
+        if self.is_void() {
+            return None;
+        }
+        let prediction = self.question.get_best_prediction_before(date)?;
+
+        match normalisation {
+            Normalisation::Raw => self.transform(&prediction),
+            Normalisation::ScaleRange => match (self.question.scale(), prediction) {
+                (Some(scale), prediction) => {
+                    let value = scale.value_of(&prediction)?;
+                    Some(scale.normalise(value).clamp(0.0, 1.0))
+                }
+                (None, NumP(p)) => Some(p),
+                _ => None,
+            },
+            Normalisation::ZScore => {
+                let value = self.transform(&prediction)?;
+                // Measure against the aggregate the prediction was taken from, which is the
+                // Metaculus prediction whenever there is one.
+                let until = self
+                    .question
+                    .close_time
+                    .map_or(date, |close| date.min(close));
+                let history = if self
+                    .question
+                    .get_metaculus_prediction_before(date)
+                    .is_some()
+                {
+                    self.transformed_history(self.question.metaculus_timeseries(), until)
+                } else {
+                    self.transformed_history(self.question.community_timeseries(), until)
+                };
+                if history.len() < 2 {
+                    return Some(0.0);
+                }
+
+                let n = history.len() as f64;
+                let mean = history.iter().sum::<f64>() / n;
+                let variance = history.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                if variance > 0.0 {
+                    Some((value - mean) / variance.sqrt())
+                } else {
+                    Some(0.0)
+                }
+            }
+        }
+    }
+
+    /// Returns the points of `history` up to `until`, in the units of the index.
+    fn transformed_history(
+        &self,
+        history: impl Iterator<Item = TimeseriesPoint>,
+        until: NaiveDateTime,
+    ) -> Vec<f64> {
+        history
+            .take_while(|point| point.time <= until)
+            .filter_map(|point| self.transform(&point.prediction))
+            .collect()
+    }
+}
@@ -0,0 +1,269 @@
+use crate::date_utils::DateUtils;
+use crate::distribution::{ContinuousDistribution, TimeseriesDistribution};
+use crate::index::{Index, WeightedQuestion};
+use crate::scale::Scale;
+use crate::{AboveUpper, BelowLower, NumP, Prediction, Quartiles};
+use chrono::NaiveDateTime;
+use rand::Rng;
+
+///
+/// The spread of the values an [Index] could take once its questions resolve, estimated by
+/// [Index::get_uncertainty_before].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexUncertainty {
+    /// The median of the sampled values.
+    pub median: f64,
+    /// The 25th and 75th percentiles of the sampled values, which contain half of them.
+    pub interval_50: (f64, f64),
+    /// The 5th and 95th percentiles of the sampled values, which contain 90% of them.
+    pub interval_90: (f64, f64),
+    ///
+    /// The ids of the unresolved questions with no community distribution to draw from, which are
+    /// held at their point forecast and so add nothing to the spread of the index.
+    ///
+    pub without_distribution: Vec<u64>,
+}
+
+impl Index {
+    ///
+    /// Estimates the spread of the current value of the index, as described in
+    /// [Index::get_uncertainty_before].
+    ///
+    pub fn get_uncertainty<R: Rng + ?Sized>(
+        &self,
+        samples: usize,
+        rng: &mut R,
+    ) -> IndexUncertainty {
+        self.get_uncertainty_before(NaiveDateTime::latest_prediction_date(), samples, rng)
+    }
+
+    ///
+    /// Estimates the spread of the values the index could take, given the community forecasts on
+    /// the given `date`, by drawing `samples` outcomes for every question from `rng`.
+    ///
+    /// Binary questions resolve yes with the probability forecast for them. Continuous questions
+    /// are drawn from the logistic recorded with their community forecast (see
+    /// [Question::get_timeseries_distribution_before]), or else from the full community
+    /// distribution if it was current on that date, or else from a logistic fitted to the
+    /// community quartiles. Questions with none of these are held at their point forecast and
+    /// listed in [IndexUncertainty::without_distribution]. Questions that had resolved by then
+    /// keep their resolution, and annulled questions are left out, as for
+    /// [Index::get_index_value_before].
+    ///
+    /// Use a seeded `rng`, such as `StdRng::seed_from_u64`, for reproducible results.
+    ///
+    /// # Example
+    /// ```rust
+    /// use metaculustetra::index::{Index, WeightedQuestion};
+    /// use metaculustetra::Question;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    ///
+    /// let file = File::open("tests/range_example.json").unwrap();
+    /// let question: Question = serde_json::from_reader(BufReader::new(file)).unwrap();
+    /// let index = Index {
+    ///     questions: vec![WeightedQuestion::create_from_range(&question, 1.0).unwrap()],
+    /// };
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let uncertainty = index.get_uncertainty(1000, &mut rng);
+    /// assert!(uncertainty.interval_90.0 <= uncertainty.interval_50.0);
+    /// assert!(uncertainty.interval_50.0 <= uncertainty.median);
+    /// ```
+    ///
+    /// [Question::get_timeseries_distribution_before]: crate::Question::get_timeseries_distribution_before
+    pub fn get_uncertainty_before<R: Rng + ?Sized>(
+        &self,
+        date: NaiveDateTime,
+        samples: usize,
+        rng: &mut R,
+    ) -> IndexUncertainty {
+        let outcomes: Vec<Outcome> = self
+            .questions
+            .iter()
+            .map(|q| q.get_outcome_before(date))
+            .collect();
+
+        let mut values: Vec<f64> = (0..samples.max(1))
+            .map(|_| {
+                self.questions
+                    .iter()
+                    .zip(&outcomes)
+                    .filter_map(|(q, outcome)| {
+                        let outcome = outcome.sample(rng)?;
+                        Some(q.transform(&outcome)? * q.weight)
+                    })
+                    .sum::<f64>()
+            })
+            .collect();
+        values.sort_by(f64::total_cmp);
+
+        IndexUncertainty {
+            median: percentile(&values, 0.5),
+            interval_50: (percentile(&values, 0.25), percentile(&values, 0.75)),
+            interval_90: (percentile(&values, 0.05), percentile(&values, 0.95)),
+            without_distribution: self
+                .questions
+                .iter()
+                .zip(&outcomes)
+                .filter(|(_, outcome)| matches!(outcome, Outcome::PointOnly(_)))
+                .map(|(q, _)| q.question.id)
+                .collect(),
+        }
+    }
+}
+
+/// What is known on some date about how a question in an index will resolve.
+enum Outcome {
+    /// The question is left out of the index.
+    Excluded,
+    /// The question has resolved.
+    Known(Prediction),
+    /// A binary question, which resolves yes with this probability.
+    Bernoulli(f64),
+    /// A continuous question, which resolves according to the logistic in its timeseries.
+    Distribution(TimeseriesDistribution),
+    /// A continuous question, which resolves according to its full community distribution.
+    Full(ContinuousDistribution),
+    /// A continuous question, which resolves according to a logistic fitted to its quartiles.
+    Quartiles(SplitLogistic),
+    /// A continuous question with no distribution, which is held at its point forecast.
+    PointOnly(Prediction),
+}
+
+impl Outcome {
+    /// Draws how the question resolves, or `None` if it is left out.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Prediction> {
+        match self {
+            Outcome::Excluded => None,
+            Outcome::Known(prediction) | Outcome::PointOnly(prediction) => Some(prediction.clone()),
+            Outcome::Bernoulli(p) => Some(NumP(if rng.random_bool(*p) { 1.0 } else { 0.0 })),
+            Outcome::Distribution(distribution) => distribution.sample(rng),
+            Outcome::Full(distribution) => {
+                let p = open_unit(rng);
+                if p < distribution.mass_below_lower_bound() {
+                    Some(BelowLower)
+                } else if p > 1.0 - distribution.mass_above_upper_bound() {
+                    Some(AboveUpper)
+                } else {
+                    distribution.quantile(p)
+                }
+            }
+            Outcome::Quartiles(logistic) => logistic.sample(rng),
+        }
+    }
+}
+
+///
+/// A logistic on the normalised scale of a question with its median and quartiles at those of the
+/// community forecast, using a different scale parameter on each side of the median so that both
+/// quartiles match.
+///
+struct SplitLogistic {
+    lower: f64,
+    median: f64,
+    upper: f64,
+    scale: Scale,
+}
+
+impl SplitLogistic {
+    /// Fits the logistic to the `quartiles` of a question on the given `scale`.
+    fn fit(quartiles: &Quartiles, scale: Scale) -> Option<SplitLogistic> {
+        let normalise = |p: &Prediction| Some(scale.normalise(scale.value_of(p)?));
+        let logistic = SplitLogistic {
+            lower: normalise(&quartiles.lower)?,
+            median: normalise(&quartiles.median)?,
+            upper: normalise(&quartiles.upper)?,
+            scale,
+        };
+        let ordered = logistic.lower <= logistic.median && logistic.median <= logistic.upper;
+        let finite = [logistic.lower, logistic.median, logistic.upper]
+            .iter()
+            .all(|x| x.is_finite());
+        if ordered && finite {
+            Some(logistic)
+        } else {
+            None
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Prediction> {
+        let p = open_unit(rng);
+        let logit = (p / (1.0 - p)).ln();
+        // A logistic is a quarter of the way up at ln(3) scale parameters below its median.
+        let spread = if logit < 0.0 {
+            self.median - self.lower
+        } else {
+            self.upper - self.median
+        };
+        let normalised = self.median + spread / 3f64.ln() * logit;
+        self.scale.prediction_at(self.scale.denormalise(normalised))
+    }
+}
+
+impl WeightedQuestion {
+    /// Returns what was known on the given `date` about how this question will resolve.
+    fn get_outcome_before(&self, date: NaiveDateTime) -> Outcome {
+        if self.is_void() {
+            return Outcome::Excluded;
+        }
+        if let Some(resolution) = self.question.get_resolution_before(date) {
+            return Outcome::Known(resolution);
+        }
+        let prediction = match self.question.get_best_prediction_before(date) {
+            Some(prediction) => prediction,
+            None => return Outcome::Excluded,
+        };
+
+        if self.question.is_binary() {
+            return match prediction {
+                NumP(p) if (0.0..=1.0).contains(&p) => Outcome::Bernoulli(p),
+                _ => Outcome::Excluded,
+            };
+        }
+        if let Some(distribution @ TimeseriesDistribution::Logistic(_)) =
+            self.question.get_timeseries_distribution_before(date)
+        {
+            return Outcome::Distribution(distribution);
+        }
+        // The full distribution is only known for the latest community forecast.
+        let latest = self.question.community_timeseries().last();
+        if latest.is_some_and(|point| point.time <= date) {
+            if let Some(distribution) = self.question.get_community_distribution() {
+                return Outcome::Full(distribution);
+            }
+        }
+        let quartiles = self.question.get_community_quartiles_before(date);
+        match (quartiles, self.question.scale()) {
+            (Some(quartiles), Some(scale)) => match SplitLogistic::fit(&quartiles, scale) {
+                Some(logistic) => Outcome::Quartiles(logistic),
+                None => Outcome::PointOnly(prediction),
+            },
+            _ => Outcome::PointOnly(prediction),
+        }
+    }
+}
+
+/// Draws a uniformly distributed number strictly between 0 and 1.
+fn open_unit<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    loop {
+        let u: f64 = rng.random();
+        if u > 0.0 {
+            return u;
+        }
+    }
+}
+
+/// Returns the `p`th quantile of the sorted `values`, interpolating linearly between them.
+fn percentile(values: &[f64], p: f64) -> f64 {
+    let position = p * (values.len() - 1) as f64;
+    let i = position.floor() as usize;
+    let fraction = position - i as f64;
+    match values.get(i + 1) {
+        Some(next) => values[i] * (1.0 - fraction) + next * fraction,
+        None => values[i],
+    }
+}
@@ -3,21 +3,56 @@
 //! [Repository](https://github.com/TetraspaceW/metaculus-tetra)
 //!
 
+#[cfg(feature = "async")]
+mod async_client;
+mod builder;
+pub mod calibration;
+pub mod conditional;
 mod date_utils;
+pub mod distribution;
+mod error;
+pub mod forecast;
 pub mod index;
+mod posts;
+pub mod query;
+mod scale;
+pub mod scoring;
+pub mod timeseries;
+pub mod transport;
 
+#[cfg(feature = "async")]
+pub use crate::async_client::AsyncMetaculus;
+use crate::builder::ClientConfig;
+pub use crate::builder::MetaculusBuilder;
+use crate::conditional::ConditionalQuestion;
 use crate::date_utils::DateUtils;
+use crate::distribution::{
+    ContinuousDistribution, LogisticMixture, TimeseriesDistribution, TimeseriesDistributionRepr,
+};
+pub use crate::error::MetaculusError;
+use crate::error::{from_json, lenient};
+use crate::forecast::{Forecast, UserPrediction};
+pub use crate::posts::ApiVersion;
+use crate::query::{QuestionIter, QuestionQuery};
+use crate::scale::Scale;
+use crate::timeseries::{Sample, TimeseriesPoint};
+use crate::transport::{HttpRequest, Transport, UreqTransport};
+use crate::FullPrediction::{NumericFP, RangeFP};
 use crate::MetaculusPredictionTimeseriesPoint::{NumericMPTP, RangeMPTP};
-use crate::Prediction::{AmbP, DatP, NumP};
+use crate::MyPredictionPoint::{NumericMyPP, RangeMyPP};
+use crate::Prediction::{AboveUpper, AmbP, Annulled, BelowLower, DatP, MultipleChoice, NumP};
 use crate::PredictionTimeseriesPoint::{NumericPTP, RangePTP};
 use crate::RangeQuestionScale::{DateRangeQuestionScale, NumericRangeQuestionScale};
-use chrono::NaiveDateTime;
+use chrono::{Duration, NaiveDateTime};
 use log::info;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 ///
-/// An API client for retrieving Metaculus question data. Contains the domain (e.g. `www`,
-/// `pandemic`, `ai`) of the Metaculus instance and the blocking reqwest client.
+/// An API client for retrieving Metaculus question data. Contains the base URL of the Metaculus
+/// instance (e.g. <https://www.metaculus.com>, <https://pandemic.metaculus.com>) and the
+/// [Transport] used to send requests.
 ///
 /// # Example
 ///
@@ -26,23 +61,73 @@ use serde::{Deserialize, Serialize};
 /// // Standard Metaculus client, accesses <https://www.metaculus.com>
 /// let m = Metaculus::standard();
 /// // Pandemic Metaculus client, accesses <https://pandemic.metaculus.com>
-/// let mp = Metaculus { domain: "pandemic" };
+/// let mp = Metaculus::with_domain("pandemic");
 /// ```
-pub struct Metaculus<'a> {
-    ///
-    /// The Metaculus [domain](https://www.metaculus.com/news/2019/08/04/introducing-the-domain-system/)
-    /// to retrieve questions from, such as `www` (Metaculus Prime), `pandemic`, or `ai`
-    ///
-    pub domain: &'a str,
+///
+/// # Migrating from 0.0.x
+///
+/// Versions before 0.1.0 were built from their public field, as in
+/// `Metaculus { domain: "pandemic" }`, and borrowed the domain with a lifetime parameter. The field
+/// and the lifetime have been removed so that clients can point at any base URL and own their
+/// [Transport]. Replace the literal with [Metaculus::new] (or [Metaculus::with_domain]), and
+/// `Metaculus<'_>` with `Metaculus`.
+///
+#[derive(Clone)]
+pub struct Metaculus {
+    config: ClientConfig,
+    transport: Arc<dyn Transport>,
+}
+
+impl Default for Metaculus {
+    /// Returns [Metaculus::standard].
+    fn default() -> Metaculus {
+        Metaculus::standard()
+    }
 }
 
-impl Metaculus<'_> {
+impl Metaculus {
     ///
     /// Returns a default Metaculus instance that retrieves questions from <https://www.metaculus.com>
-    /// with a newly created [reqwest::blocking::Client] instance.
+    /// with a newly created [UreqTransport].
+    ///
+    pub fn standard() -> Metaculus {
+        Metaculus::with_domain("www")
+    }
+
+    ///
+    /// Returns a Metaculus instance that retrieves questions from the given `domain`, the
+    /// replacement for the `Metaculus { domain }` literal of earlier versions. Equivalent to
+    /// [Metaculus::with_domain].
     ///
-    pub fn standard() -> Metaculus<'static> {
-        Metaculus { domain: "www" }
+    pub fn new(domain: &str) -> Metaculus {
+        Metaculus::with_domain(domain)
+    }
+
+    ///
+    /// Returns a Metaculus instance that retrieves questions from the given Metaculus
+    /// [domain](https://www.metaculus.com/news/2019/08/04/introducing-the-domain-system/), such as
+    /// `www` (Metaculus Prime), `pandemic`, or `ai`.
+    ///
+    pub fn with_domain(domain: &str) -> Metaculus {
+        Metaculus {
+            config: ClientConfig::for_domain(domain),
+            transport: Arc::new(UreqTransport::default()),
+        }
+    }
+
+    /// Returns a [MetaculusBuilder] for configuring a new client.
+    pub fn builder() -> MetaculusBuilder {
+        MetaculusBuilder::default()
+    }
+
+    /// Returns the base URL that API paths are appended to.
+    pub fn base_url(&self) -> &str {
+        &self.config.base_url
+    }
+
+    /// Returns the generation of the Metaculus API that questions are requested from.
+    pub fn api_version(&self) -> ApiVersion {
+        self.config.api_version
     }
 
     ///
@@ -50,7 +135,18 @@ impl Metaculus<'_> {
     /// `None` otherwise.
     ///
     pub fn get_numeric_prediction_for(&self, id: &str) -> Option<f64> {
-        self.get_prediction_for(id)?.get_if_numeric()
+        self.try_get_numeric_prediction_for(id).ok()?
+    }
+
+    ///
+    /// Returns the numeric prediction for the question `id` if it is a numerical question, `None`
+    /// if it is not numerical or has no predictions, and a [MetaculusError] if the question could
+    /// not be retrieved.
+    ///
+    pub fn try_get_numeric_prediction_for(&self, id: &str) -> Result<Option<f64>, MetaculusError> {
+        Ok(self
+            .try_get_prediction_for(id)?
+            .and_then(|p| p.get_if_numeric()))
     }
 
     ///
@@ -58,7 +154,21 @@ impl Metaculus<'_> {
     /// otherwise.
     ///
     pub fn get_date_prediction_for(&self, id: &str) -> Option<NaiveDateTime> {
-        self.get_prediction_for(id)?.get_if_date()
+        self.try_get_date_prediction_for(id).ok()?
+    }
+
+    ///
+    /// Returns the date prediction for the question `id` if it is a date question, `None` if it is
+    /// not a date question or has no predictions, and a [MetaculusError] if the question could not
+    /// be retrieved.
+    ///
+    pub fn try_get_date_prediction_for(
+        &self,
+        id: &str,
+    ) -> Result<Option<NaiveDateTime>, MetaculusError> {
+        Ok(self
+            .try_get_prediction_for(id)?
+            .and_then(|p| p.get_if_date()))
     }
 
     ///
@@ -67,39 +177,272 @@ impl Metaculus<'_> {
     /// if the question exists and has any predictions.
     ///
     pub fn get_prediction_for(&self, id: &str) -> Option<Prediction> {
-        self.get_question(id)?.get_best_prediction()
+        self.try_get_prediction_for(id).ok()?
+    }
+
+    ///
+    /// Returns the best prediction available for the question with id `id` as a [Prediction], `None`
+    /// if the question has no predictions, and a [MetaculusError] if the question could not be
+    /// retrieved.
+    ///
+    pub fn try_get_prediction_for(&self, id: &str) -> Result<Option<Prediction>, MetaculusError> {
+        Ok(self.try_get_question(id)?.get_best_prediction())
     }
 
     ///
     /// Returns the question with id `id` as a [Question] if it exists.
     ///
     pub fn get_question(&self, id: &str) -> Option<Question> {
-        let url = format!(
-            "https://{}.metaculus.com/api2/questions/{}",
-            self.domain, id
-        );
-        let response = ureq::get(&url).call().ok()?.into_json().ok()?;
+        self.try_get_question(id).ok()
+    }
+
+    ///
+    /// Returns the question with id `id` as a [Question], or a [MetaculusError] describing why it
+    /// could not be retrieved.
+    ///
+    pub fn try_get_question(&self, id: &str) -> Result<Question, MetaculusError> {
+        let payload = self.get_json(&self.config.question_url(id), id)?;
+        let question = posts::question_from_value(payload, self.config.api_version)?;
         info!("Question id {} retrieved successfully.", id);
 
-        Some(response)
+        Ok(question)
+    }
+
+    ///
+    /// Returns the conditional question pair with id `id` as a [ConditionalQuestion] if it exists.
+    ///
+    pub fn get_conditional(&self, id: &str) -> Option<ConditionalQuestion> {
+        self.try_get_conditional(id).ok()
+    }
+
+    ///
+    /// Returns the conditional question pair with id `id` as a [ConditionalQuestion], or a
+    /// [MetaculusError] describing why it could not be retrieved.
+    ///
+    pub fn try_get_conditional(&self, id: &str) -> Result<ConditionalQuestion, MetaculusError> {
+        let payload = self.get_json(&self.config.question_url(id), id)?;
+        let conditional = posts::conditional_from_value(payload, self.config.api_version)?;
+        info!("Conditional question id {} retrieved successfully.", id);
+
+        Ok(conditional)
+    }
+
+    ///
+    /// Submits the authenticated user's `forecast` on the question with id `id`, after checking
+    /// that it is valid for the question (see [Forecast::validate_for]). Requires a client with an
+    /// API token (see [MetaculusBuilder::token]).
+    ///
+    /// Forecasts are submitted to the legacy API by question id, so a client using
+    /// [ApiVersion::Posts] (whose ids are post ids) returns a [MetaculusError::InvalidForecast]
+    /// without sending anything.
+    ///
+    /// # Example
+    ///
+    /// ``` rust,no_run
+    /// use metaculustetra::distribution::{LogisticComponent, LogisticMixture};
+    /// use metaculustetra::Metaculus;
+    ///
+    /// let m = Metaculus::builder().token("my-api-token").build().unwrap();
+    /// // 30% on a binary question
+    /// m.submit_probability("8541", 0.3).unwrap();
+    /// // A logistic centred 20% of the way along the scale of a continuous question
+    /// let distribution = LogisticMixture::new(vec![LogisticComponent { x0: 0.2, s: 0.05, w: 1.0 }]);
+    /// m.submit_distribution("7887", &distribution).unwrap();
+    /// ```
+    pub fn submit_forecast(&self, id: &str, forecast: &Forecast) -> Result<(), MetaculusError> {
+        if self.config.api_version == ApiVersion::Posts {
+            return Err(MetaculusError::InvalidForecast(format!(
+                "{} is a post id, but forecasts can only be submitted by question id with the \
+                 legacy API",
+                id
+            )));
+        }
+        let question = self.try_get_question(id)?;
+        forecast.validate_for(&question)?;
+
+        let request = HttpRequest {
+            url: self.config.predict_url(id),
+            headers: self.config.headers(),
+        };
+        let body = forecast.to_submission(&question).to_string();
+        self.transport.post(&request, &body)?.into_body(id)?;
+        info!("Forecast on question id {} submitted successfully.", id);
+
+        Ok(())
+    }
+
+    ///
+    /// Submits the authenticated user's `probability` (from 0.01 to 0.99) on the binary question
+    /// with id `id`.
+    ///
+    pub fn submit_probability(&self, id: &str, probability: f64) -> Result<(), MetaculusError> {
+        self.submit_forecast(id, &Forecast::Probability(probability))
+    }
+
+    ///
+    /// Submits the authenticated user's `distribution` on the continuous (numeric or date) question
+    /// with id `id`.
+    ///
+    pub fn submit_distribution(
+        &self,
+        id: &str,
+        distribution: &LogisticMixture,
+    ) -> Result<(), MetaculusError> {
+        self.submit_forecast(id, &Forecast::Continuous(distribution.clone()))
+    }
+
+    ///
+    /// Returns a lazy iterator over the questions matching `query`, transparently following the
+    /// pagination links returned by Metaculus.
+    ///
+    /// # Example
+    ///
+    /// ``` rust,no_run
+    /// use metaculustetra::query::QuestionQuery;
+    /// use metaculustetra::Metaculus;
+    ///
+    /// let m = Metaculus::standard();
+    /// let query = QuestionQuery::new().project("1007").status("open");
+    /// for question in m.list_questions(&query).take(50) {
+    ///     println!("{}", question.unwrap().title_short);
+    /// }
+    /// ```
+    pub fn list_questions(&self, query: &QuestionQuery) -> QuestionIter<'_> {
+        QuestionIter::new(self, self.config.list_url(query))
+    }
+
+    ///
+    /// Returns a client with the same settings and transport for the given Metaculus `domain`, as
+    /// described in [ClientConfig::on_domain].
+    ///
+    pub(crate) fn on_domain(&self, domain: &str) -> Result<Metaculus, MetaculusError> {
+        Ok(Metaculus {
+            config: self.config.on_domain(domain)?,
+            transport: self.transport.clone(),
+        })
+    }
+
+    ///
+    /// Sends a `GET` request to `url` and deserializes the response, returning
+    /// [MetaculusError::NotFound] with the given `id` if there is nothing at that URL.
+    ///
+    pub(crate) fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        id: &str,
+    ) -> Result<T, MetaculusError> {
+        let request = HttpRequest {
+            url: url.to_string(),
+            headers: self.config.headers(),
+        };
+        self.transport.get(&request)?.into_json(id)
     }
 }
 
+/// The resolution code Metaculus uses for questions that resolved ambiguously.
+pub(crate) const AMBIGUOUS_RESOLUTION: f64 = -1.0;
+/// The resolution code Metaculus uses for questions that were annulled.
+pub(crate) const ANNULLED_RESOLUTION: f64 = -2.0;
+
 ///
 /// Data on a single Metaculus question.
 ///
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Question {
+    /// The id of the question, as used in its URL.
+    #[serde(default, deserialize_with = "lenient")]
+    pub id: u64,
+    /// The full title of the question.
+    #[serde(default, deserialize_with = "lenient")]
+    pub title: String,
     /// The title of the question displayed on Metaculus.
     pub title_short: String,
+    /// The URL of the question in the API.
+    #[serde(default, deserialize_with = "lenient")]
+    pub url: String,
+    /// The path of the question's page on Metaculus, relative to the domain.
+    #[serde(default, deserialize_with = "lenient")]
+    pub page_url: String,
+    /// The username of the question's author.
+    #[serde(default, deserialize_with = "lenient")]
+    pub author_name: String,
+    /// When the question was created.
+    #[serde(default, with = "metaculus_time")]
+    pub created_time: Option<NaiveDateTime>,
+    /// When the question opened for predictions.
+    #[serde(default, with = "metaculus_time")]
+    pub publish_time: Option<NaiveDateTime>,
+    /// When the question closes (or closed) for predictions.
+    #[serde(default, with = "metaculus_time")]
+    pub close_time: Option<NaiveDateTime>,
+    /// When the question resolves (or resolved).
+    #[serde(default, with = "metaculus_time")]
+    pub resolve_time: Option<NaiveDateTime>,
+    /// Whether the question has been approved by the moderators.
+    #[serde(default, deserialize_with = "lenient")]
+    pub status: PublicationStatus,
+    /// The kind of page, such as a forecast or a discussion.
+    #[serde(default, rename = "type", deserialize_with = "lenient")]
+    pub kind: QuestionKind,
+    /// The categories the question is in, such as `computing--ai`.
+    #[serde(default, deserialize_with = "lenient")]
+    pub categories: Vec<String>,
+    /// The projects (such as tournaments) the question is in.
+    #[serde(default, deserialize_with = "lenient")]
+    pub projects: Vec<Project>,
+    /// The number of comments on the question.
+    #[serde(default, deserialize_with = "lenient")]
+    pub comment_count: u64,
+    /// The net number of upvotes on the question.
+    #[serde(default, deserialize_with = "lenient")]
+    pub votes: i64,
+    /// The total number of predictions made on the question, including updates.
+    #[serde(default, deserialize_with = "lenient")]
+    pub number_of_predictions: u64,
+    /// How active the question has been recently, as used to order questions on Metaculus.
+    #[serde(default, deserialize_with = "lenient")]
+    pub activity: f64,
+    /// The description of the question, as HTML (or Markdown, from the posts-based API).
+    #[serde(default, deserialize_with = "lenient")]
+    pub description_html: String,
+    ///
+    /// The questions making up a group (or multiple-choice) question, one for each option. Empty
+    /// for any other kind of question. The options of a multiple-choice question from the
+    /// posts-based API have no ids of their own, so they are given distinct made-up ids with the
+    /// highest bit set.
+    ///
+    #[serde(default)]
+    pub sub_questions: Vec<Question>,
+    /// The option this question represents, if it is one of the sub-questions of a group.
+    #[serde(default, deserialize_with = "lenient")]
+    pub sub_question_label: Option<String>,
     prediction_timeseries: Option<Vec<PredictionTimeseriesPoint>>,
     metaculus_prediction: Option<MetaculusPrediction>,
     resolution: Option<f64>,
     possibilities: QuestionPossibilities,
-    resolve_time: Option<String>,
+    #[serde(default)]
+    my_predictions: Option<MyPredictions>,
+    #[serde(default)]
+    community_prediction: Option<CommunityPrediction>,
 }
 
 impl Question {
+    ///
+    /// Parses a question from a JSON response `body` from the given generation of the Metaculus
+    /// API, such as a saved response. [ApiVersion::Auto] accepts either generation.
+    ///
+    /// # Example
+    /// ```rust
+    /// use metaculustetra::{ApiVersion, Question};
+    ///
+    /// let body = std::fs::read_to_string("tests/probability_example.json").unwrap();
+    /// let question = Question::from_json(&body, ApiVersion::Auto).unwrap();
+    /// assert!(question.is_binary());
+    /// ```
+    pub fn from_json(body: &str, version: ApiVersion) -> Result<Question, MetaculusError> {
+        posts::question_from_value(from_json(body)?, version)
+    }
+
     ///
     /// Returns the best prediction available (prioritising the actual resolution, then the
     /// Metaculus prediction, then the community prediction) for the question as a [Prediction], if
@@ -156,42 +499,80 @@ impl Question {
     }
 
     fn convert_range_prediction(&self, prediction: f64) -> Option<Prediction> {
-        let scale = self.possibilities.scale.as_ref()?;
-
-        match scale {
-            NumericRangeQuestionScale { min, max, .. } => {
-                Some(NumP(self.scale_range_prediction(prediction, *min, *max)))
-            }
-            DateRangeQuestionScale { min, max, .. } => {
-                let min_ts = NaiveDateTime::date_to_timestamp(min)?;
-                let max_ts = NaiveDateTime::date_to_timestamp(max)?;
-                Some(DatP(NaiveDateTime::from_timestamp_opt(
-                    self.scale_range_prediction(prediction, min_ts, max_ts) as i64,
-                    0,
-                )?))
-            }
-        }
+        let scale = self.scale()?;
+        scale.prediction_at(scale.denormalise(prediction))
     }
 
-    fn scale_range_prediction(&self, prediction: f64, min: f64, max: f64) -> f64 {
-        if self.is_logarithmic() {
-            (max / min).powf(prediction) * min
-        } else {
-            prediction * (max - min) + min
+    pub(crate) fn scale(&self) -> Option<Scale> {
+        let logarithmic = self.is_logarithmic();
+
+        match self.possibilities.scale.as_ref()? {
+            NumericRangeQuestionScale {
+                min,
+                max,
+                zero_point,
+                ..
+            } => Some(Scale {
+                min: *min,
+                max: *max,
+                logarithmic,
+                dates: false,
+                zero_point: zero_point.unwrap_or(0.0),
+            }),
+            DateRangeQuestionScale {
+                min,
+                max,
+                zero_point,
+                ..
+            } => Some(Scale {
+                min: NaiveDateTime::date_to_timestamp(min)?,
+                max: NaiveDateTime::date_to_timestamp(max)?,
+                logarithmic,
+                dates: true,
+                zero_point: zero_point.unwrap_or(0.0),
+            }),
         }
     }
 
     ///
     /// Returns the question resolution, if it exists. This will be a [Prediction::AmbP] if the
-    /// question has resolved ambiguously.
+    /// question has resolved ambiguously, [Prediction::Annulled] if it has been annulled, and
+    /// [Prediction::BelowLower] or [Prediction::AboveUpper]
+    /// if a continuous question has resolved beyond one of its open bounds.
     ///
     pub fn get_resolution(&self) -> Option<Prediction> {
-        if self.resolution? == -1.0 {
+        if self.is_group() {
+            return self.get_group_resolution(Question::get_resolution);
+        }
+
+        let resolution = self.resolution?;
+        if resolution == AMBIGUOUS_RESOLUTION {
             Some(AmbP)
+        } else if resolution == ANNULLED_RESOLUTION {
+            Some(Annulled)
         } else if self.possibilities.question_type == "continuous" {
-            Some(self.convert_range_prediction(self.resolution?)?)
+            // Resolutions beyond the scale are only meaningful if that bound is open; otherwise
+            // they are taken to be at the bound.
+            if resolution < 0.0 {
+                if self.has_open_lower_bound() {
+                    Some(BelowLower)
+                } else {
+                    self.convert_range_prediction(0.0)
+                }
+            } else if resolution > 1.0 {
+                if self.has_open_upper_bound() {
+                    Some(AboveUpper)
+                } else {
+                    self.convert_range_prediction(1.0)
+                }
+            } else {
+                self.convert_range_prediction(resolution)
+            }
+        } else if resolution == 0.0 || resolution == 1.0 {
+            Some(NumP(resolution))
         } else {
-            Some(NumP(self.resolution?))
+            // Any other code is not a resolution this crate knows how to interpret.
+            None
         }
     }
 
@@ -204,21 +585,345 @@ impl Question {
         }
     }
 
+    ///
+    /// Returns `true` iff the question is continuous and allows predictions below the minimum of its
+    /// scale.
+    ///
+    pub fn has_open_lower_bound(&self) -> bool {
+        self.possibilities.low
+    }
+
+    ///
+    /// Returns `true` iff the question is continuous and allows predictions above the maximum of its
+    /// scale.
+    ///
+    pub fn has_open_upper_bound(&self) -> bool {
+        self.possibilities.high
+    }
+
+    ///
+    /// Returns where a numeric or date `prediction` lies on the scale of a continuous question,
+    /// where `0.0` is the minimum and `1.0` is the maximum, accounting for logarithmic scales. This
+    /// is the inverse of how Metaculus' normalised predictions are converted into real units.
+    ///
+    /// Returns `None` if the question is not continuous or the prediction is of the wrong type.
+    ///
+    pub fn normalise(&self, prediction: &Prediction) -> Option<f64> {
+        let scale = self.scale()?;
+        Some(scale.normalise(scale.value_of(prediction)?))
+    }
+
     /// Returns `true` iff the question is a binary probability question.
     pub fn is_binary(&self) -> bool {
         self.possibilities.question_type == *"binary"
     }
 
     ///
-    /// Returns the community median prediction sa it was on the given `date`, if it existed.
+    /// Returns `true` iff the question is a group (or multiple-choice) question, made up of
+    /// [Question::sub_questions].
+    ///
+    pub fn is_group(&self) -> bool {
+        !self.sub_questions.is_empty()
+    }
+
+    /// Returns the label of the option this question represents in a group, or its title.
+    fn label(&self) -> &str {
+        self.sub_question_label
+            .as_deref()
+            .unwrap_or(&self.title_short)
+    }
+
+    ///
+    /// Returns a [Prediction::MultipleChoice] made up of the `resolution` of each sub-question, or
+    /// `None` unless every one of them has resolved yes or no. If every sub-question resolved
+    /// ambiguously or was annulled, that is the resolution of the group.
+    ///
+    fn get_group_resolution(
+        &self,
+        resolution: impl Fn(&Question) -> Option<Prediction>,
+    ) -> Option<Prediction> {
+        let resolutions: Vec<Option<Prediction>> =
+            self.sub_questions.iter().map(resolution).collect();
+        if let Some(Some(void @ (AmbP | Annulled))) = resolutions.first() {
+            if resolutions.iter().all(|r| r.as_ref() == Some(void)) {
+                return Some(void.clone());
+            }
+        }
+
+        let options: Option<Vec<(String, f64)>> = self
+            .sub_questions
+            .iter()
+            .zip(&resolutions)
+            .map(|(q, r)| Some((q.label().to_string(), r.as_ref()?.get_if_numeric()?)))
+            .collect();
+        Some(MultipleChoice(options?))
+    }
+
+    ///
+    /// Returns a [Prediction::MultipleChoice] made up of the `prediction` for each sub-question, or
+    /// `None` if any of them is not binary or has no such prediction, since the options would not
+    /// then cover every outcome.
+    ///
+    fn get_group_prediction(
+        &self,
+        prediction: impl Fn(&Question) -> Option<Prediction>,
+    ) -> Option<Prediction> {
+        let options: Option<Vec<(String, f64)>> = self
+            .sub_questions
+            .iter()
+            .map(|q| {
+                if !q.is_binary() {
+                    return None;
+                }
+                Some((q.label().to_string(), prediction(q)?.get_if_numeric()?))
+            })
+            .collect();
+
+        match options? {
+            options if options.is_empty() => None,
+            options => Some(MultipleChoice(options)),
+        }
+    }
+
+    ///
+    /// Returns the community median prediction sa it was on the given `date`, if it existed. The
+    /// community prediction stops updating once the question closes, so any later `date` gives
+    /// the prediction at close.
     ///
     pub fn get_community_prediction_before(&self, date: NaiveDateTime) -> Option<Prediction> {
-        let predictions = self.prediction_timeseries.as_ref()?;
-        match predictions
+        if self.is_group() {
+            return self.get_group_prediction(|q| q.get_community_prediction_before(date));
+        }
+        self.community_prediction_at(self.community_point_before(date)?)
+    }
+
+    ///
+    /// Returns the Metaculus prediction as it was on the given `date`, if it existed. As with
+    /// [Question::get_community_prediction_before], predictions after the question closes are
+    /// ignored.
+    ///
+    pub fn get_metaculus_prediction_before(&self, date: NaiveDateTime) -> Option<Prediction> {
+        if self.is_group() {
+            return self.get_group_prediction(|q| q.get_metaculus_prediction_before(date));
+        }
+        self.metaculus_prediction_at(self.metaculus_point_before(date)?)
+    }
+
+    ///
+    /// Returns every point in the history of the community prediction, oldest first, including
+    /// the quartiles and number of predictions where Metaculus reports them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    /// use metaculustetra::Question;
+    ///
+    /// let file = File::open("tests/probability_example.json").unwrap();
+    /// let question: Question = serde_json::from_reader(BufReader::new(file)).unwrap();
+    /// for point in question.community_timeseries() {
+    ///     println!("{}: {:?}", point.time, point.prediction);
+    /// }
+    /// ```
+    pub fn community_timeseries(&self) -> impl Iterator<Item = TimeseriesPoint> + '_ {
+        self.prediction_timeseries
             .iter()
-            .rev()
-            .find(|it| it.timestamp() <= date.timestamp() as f64)?
-        {
+            .flatten()
+            .filter_map(move |point| {
+                Some(TimeseriesPoint {
+                    time: NaiveDateTime::timestamp_to_date(point.timestamp())?,
+                    prediction: self.community_prediction_at(point)?,
+                    quartiles: self.community_quartiles_at(point),
+                    num_predictions: point.num_predictions(),
+                })
+            })
+    }
+
+    ///
+    /// Samples the community prediction at regular `interval`s from `start` to `end` (inclusive),
+    /// with the same semantics as [Question::get_community_prediction_before]: each sample is the
+    /// latest prediction made by then, or `None` if there was none.
+    ///
+    /// # Example
+    /// ```rust
+    /// use chrono::{Duration, NaiveDate};
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    /// use metaculustetra::Question;
+    ///
+    /// let file = File::open("tests/probability_example.json").unwrap();
+    /// let question: Question = serde_json::from_reader(BufReader::new(file)).unwrap();
+    ///
+    /// // Weekly community predictions during 2020
+    /// let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2020, 12, 31).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let weekly = question.resample(start, end, Duration::weeks(1));
+    /// assert_eq!(weekly.len(), 53);
+    /// ```
+    pub fn resample(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        interval: Duration,
+    ) -> Vec<Sample<Option<Prediction>>> {
+        timeseries::grid(start, end, interval)
+            .map(|time| Sample {
+                time,
+                value: self.get_community_prediction_before(time),
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns every point in the history of the Metaculus prediction, oldest first, including the
+    /// quartiles for continuous questions.
+    ///
+    pub fn metaculus_timeseries(&self) -> impl Iterator<Item = TimeseriesPoint> + '_ {
+        self.metaculus_prediction
+            .iter()
+            .flat_map(|prediction| prediction.history.iter())
+            .filter_map(move |point| {
+                Some(TimeseriesPoint {
+                    time: NaiveDateTime::timestamp_to_date(point.timestamp())?,
+                    prediction: self.metaculus_prediction_at(point)?,
+                    quartiles: self.metaculus_quartiles_at(point),
+                    num_predictions: None,
+                })
+            })
+    }
+
+    ///
+    /// Returns the full community distribution, if the question is continuous and the community
+    /// prediction is available.
+    ///
+    pub fn get_community_distribution(&self) -> Option<ContinuousDistribution> {
+        self.get_distribution(self.community_prediction.as_ref()?.full.as_ref()?)
+    }
+
+    ///
+    /// Returns the full Metaculus prediction distribution, if the question is continuous and the
+    /// Metaculus prediction is available.
+    ///
+    pub fn get_metaculus_distribution(&self) -> Option<ContinuousDistribution> {
+        self.get_distribution(self.metaculus_prediction.as_ref()?.full.as_ref()?)
+    }
+
+    ///
+    /// Returns the latest community forecast recorded in the prediction timeseries as a
+    /// [TimeseriesDistribution], if there is one.
+    ///
+    pub fn get_timeseries_distribution(&self) -> Option<TimeseriesDistribution> {
+        self.get_timeseries_distribution_before(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Returns the community forecast recorded in the prediction timeseries as it was on the given
+    /// `date`, if it existed. Unlike [Question::get_community_quartiles_before], this allows any
+    /// percentile to be computed.
+    ///
+    pub fn get_timeseries_distribution_before(
+        &self,
+        date: NaiveDateTime,
+    ) -> Option<TimeseriesDistribution> {
+        self.community_point_before(date)?
+            .distribution()?
+            .with_scale(self.scale())
+    }
+
+    fn get_distribution(&self, full: &FullPrediction) -> Option<ContinuousDistribution> {
+        match full {
+            RangeFP { y, low, high } => ContinuousDistribution::new(
+                y,
+                low.unwrap_or(0.0),
+                high.unwrap_or(1.0),
+                self.scale()?,
+            ),
+            NumericFP(_) => None,
+        }
+    }
+
+    ///
+    /// Returns the history of the authenticated user's own predictions on the question, oldest
+    /// first. This is empty unless the question was retrieved by a client with an API token (see
+    /// [MetaculusBuilder::token]) and the user has predicted on it.
+    ///
+    pub fn get_my_predictions(&self) -> Vec<UserPrediction> {
+        let predictions = match self.my_predictions.as_ref() {
+            Some(my_predictions) => &my_predictions.predictions,
+            None => return vec![],
+        };
+
+        predictions
+            .iter()
+            .filter_map(|it| match it {
+                NumericMyPP { t, x } => Some(UserPrediction {
+                    time: NaiveDateTime::timestamp_to_date(*t)?,
+                    forecast: Forecast::Probability(*x),
+                }),
+                RangeMyPP { t, d } => Some(UserPrediction {
+                    time: NaiveDateTime::timestamp_to_date(*t)?,
+                    forecast: Forecast::Continuous(d.clone()),
+                }),
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the lower quartile, median and upper quartile of the community prediction, if it
+    /// exists.
+    ///
+    pub fn get_community_quartiles(&self) -> Option<Quartiles> {
+        self.get_community_quartiles_before(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Returns the lower quartile, median and upper quartile of the community prediction as it was
+    /// on the given `date`, if it existed. For binary questions, these are the quartiles of the
+    /// individual predictions making up the community prediction.
+    ///
+    pub fn get_community_quartiles_before(&self, date: NaiveDateTime) -> Option<Quartiles> {
+        self.community_quartiles_at(self.community_point_before(date)?)
+    }
+
+    ///
+    /// Returns the lower quartile, median and upper quartile of the Metaculus prediction, if it
+    /// exists and the question is continuous.
+    ///
+    pub fn get_metaculus_quartiles(&self) -> Option<Quartiles> {
+        self.get_metaculus_quartiles_before(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Returns the lower quartile, median and upper quartile of the Metaculus prediction as it was
+    /// on the given `date`, if it existed and the question is continuous.
+    ///
+    pub fn get_metaculus_quartiles_before(&self, date: NaiveDateTime) -> Option<Quartiles> {
+        self.metaculus_quartiles_at(self.metaculus_point_before(date)?)
+    }
+
+    /// Returns the last point in the community timeseries by the given `date`.
+    fn community_point_before(&self, date: NaiveDateTime) -> Option<&PredictionTimeseriesPoint> {
+        latest_by(
+            self.prediction_timeseries.as_ref()?,
+            self.last_update_before(date),
+            PredictionTimeseriesPoint::timestamp,
+        )
+    }
+
+    /// Returns the last point in the Metaculus prediction history by the given `date`.
+    fn metaculus_point_before(
+        &self,
+        date: NaiveDateTime,
+    ) -> Option<&MetaculusPredictionTimeseriesPoint> {
+        latest_by(
+            &self.metaculus_prediction.as_ref()?.history,
+            self.last_update_before(date),
+            MetaculusPredictionTimeseriesPoint::timestamp,
+        )
+    }
+
+    fn community_prediction_at(&self, point: &PredictionTimeseriesPoint) -> Option<Prediction> {
+        match point {
             NumericPTP {
                 community_prediction,
                 ..
@@ -231,24 +936,109 @@ impl Question {
     }
 
     ///
-    /// Returns the Metaculus prediction as it was on the given `date`, if it existed.
+    /// Returns the quartiles of the community prediction at the given point. For binary questions,
+    /// these are recorded separately, in the history of the community prediction.
     ///
-    pub fn get_metaculus_prediction_before(&self, date: NaiveDateTime) -> Option<Prediction> {
-        let mut metaculus_predictions = self.metaculus_prediction.as_ref()?.history.clone();
-        metaculus_predictions.reverse();
-        match metaculus_predictions
-            .iter()
-            .find(|it| it.timestamp() <= date.timestamp() as f64)?
-        {
+    fn community_quartiles_at(&self, point: &PredictionTimeseriesPoint) -> Option<Quartiles> {
+        match point {
+            NumericPTP { t, .. } => {
+                let history = &self.community_prediction.as_ref()?.history;
+                let x1 = &latest_by(history, *t, |it| it.t)?.x1;
+                Some(Quartiles {
+                    lower: NumP(x1.q1),
+                    median: NumP(x1.q2),
+                    upper: NumP(x1.q3),
+                })
+            }
+            RangePTP {
+                community_prediction,
+                ..
+            } => self.convert_range_quartiles(
+                community_prediction.q1?,
+                community_prediction.q2,
+                community_prediction.q3?,
+            ),
+        }
+    }
+
+    fn metaculus_prediction_at(
+        &self,
+        point: &MetaculusPredictionTimeseriesPoint,
+    ) -> Option<Prediction> {
+        match point {
             NumericMPTP { x, .. } => Some(NumP(*x)),
             RangeMPTP { x, .. } => self.convert_range_prediction(x.q2),
         }
     }
 
+    fn metaculus_quartiles_at(
+        &self,
+        point: &MetaculusPredictionTimeseriesPoint,
+    ) -> Option<Quartiles> {
+        match point {
+            RangeMPTP { x, .. } => self.convert_range_quartiles(x.q1?, x.q2, x.q3?),
+            NumericMPTP { .. } => None,
+        }
+    }
+
+    fn convert_range_quartiles(&self, q1: f64, q2: f64, q3: f64) -> Option<Quartiles> {
+        Some(Quartiles {
+            lower: self.convert_range_prediction(q1)?,
+            median: self.convert_range_prediction(q2)?,
+            upper: self.convert_range_prediction(q3)?,
+        })
+    }
+
+    ///
+    /// Returns the state the question is in now, such as [QuestionStatus::Open].
+    ///
+    pub fn status(&self) -> QuestionStatus {
+        self.status_at(NaiveDateTime::latest_prediction_date())
+    }
+
+    ///
+    /// Returns the state the question was in on the given `date`, based on when it was published,
+    /// closed and resolved. Missing times are treated as not restricting the question, so a
+    /// question with no publish time is taken to have always been published.
+    ///
+    pub fn status_at(&self, date: NaiveDateTime) -> QuestionStatus {
+        if self.publish_time.is_some_and(|t| date < t) {
+            return QuestionStatus::Upcoming;
+        }
+
+        if self.resolve_time.is_none_or(|t| t <= date) {
+            match self.get_resolution() {
+                Some(AmbP) => return QuestionStatus::Ambiguous,
+                Some(Annulled) => return QuestionStatus::Annulled,
+                Some(_) => return QuestionStatus::Resolved,
+                None => {}
+            }
+        }
+
+        if self.close_time.is_some_and(|t| t <= date) {
+            QuestionStatus::Closed
+        } else {
+            QuestionStatus::Open
+        }
+    }
+
+    ///
+    /// Returns the timestamp of the last moment predictions could have been updated by the given
+    /// `date`, which is the close time if the question has closed by then.
+    ///
+    fn last_update_before(&self, date: NaiveDateTime) -> f64 {
+        let date = match self.close_time {
+            Some(close_time) => date.min(close_time),
+            None => date,
+        };
+        date.and_utc().timestamp_micros() as f64 / 1e6
+    }
+
     fn get_resolution_before(&self, date: NaiveDateTime) -> Option<Prediction> {
-        if NaiveDateTime::parse_from_str(self.resolve_time.as_ref()?, "%Y-%m-%dT%H:%M:%SZ").ok()?
-            <= date
-        {
+        if self.is_group() {
+            return self.get_group_resolution(|q| q.get_resolution_before(date));
+        }
+        if self.resolve_time? <= date {
             self.get_resolution()
         } else {
             None
@@ -256,17 +1046,169 @@ impl Question {
     }
 }
 
+///
+/// The stages of a question's lifecycle, as returned by [Question::status_at].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuestionStatus {
+    /// The question has not been published yet.
+    Upcoming,
+    /// The question is accepting predictions.
+    Open,
+    /// The question has closed and is awaiting resolution.
+    Closed,
+    /// The question has resolved.
+    Resolved,
+    /// The question resolved ambiguously, so it has no meaningful resolution.
+    Ambiguous,
+    /// The question was annulled, so it has no meaningful resolution.
+    Annulled,
+}
+
+///
+/// Whether a question has been approved by the moderators, from the `status` field returned by
+/// Metaculus. This is not whether the question is open; see [Question::status] for that.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(from = "String", into = "String")]
+pub enum PublicationStatus {
+    /// The question has been approved and is visible to everyone.
+    Approved,
+    /// The question is awaiting review by the moderators.
+    Pending,
+    /// The question is a draft that has not been submitted for review.
+    Draft,
+    /// A status code this crate does not know about, or none at all.
+    Other(String),
+}
+
+impl Default for PublicationStatus {
+    fn default() -> Self {
+        PublicationStatus::Other(String::new())
+    }
+}
+
+impl From<String> for PublicationStatus {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "A" => PublicationStatus::Approved,
+            "P" => PublicationStatus::Pending,
+            "D" => PublicationStatus::Draft,
+            _ => PublicationStatus::Other(status),
+        }
+    }
+}
+
+impl From<PublicationStatus> for String {
+    fn from(status: PublicationStatus) -> Self {
+        match status {
+            PublicationStatus::Approved => "A".to_string(),
+            PublicationStatus::Pending => "P".to_string(),
+            PublicationStatus::Draft => "D".to_string(),
+            PublicationStatus::Other(status) => status,
+        }
+    }
+}
+
+///
+/// The kind of page a question is, from the `type` field returned by Metaculus.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(from = "String", into = "String")]
+pub enum QuestionKind {
+    /// A question that can be predicted on.
+    Forecast,
+    /// A page for discussion, with no predictions.
+    Discussion,
+    /// A notebook, such as an essay or announcement.
+    Notebook,
+    /// A group (or multiple-choice) question, made up of sub-questions.
+    Group,
+    /// A kind this crate does not know about, or none at all.
+    Other(String),
+}
+
+impl Default for QuestionKind {
+    fn default() -> Self {
+        QuestionKind::Other(String::new())
+    }
+}
+
+impl From<String> for QuestionKind {
+    fn from(kind: String) -> Self {
+        match kind.as_str() {
+            "forecast" => QuestionKind::Forecast,
+            "discussion" => QuestionKind::Discussion,
+            "notebook" => QuestionKind::Notebook,
+            "group" => QuestionKind::Group,
+            _ => QuestionKind::Other(kind),
+        }
+    }
+}
+
+impl From<QuestionKind> for String {
+    fn from(kind: QuestionKind) -> Self {
+        match kind {
+            QuestionKind::Forecast => "forecast".to_string(),
+            QuestionKind::Discussion => "discussion".to_string(),
+            QuestionKind::Notebook => "notebook".to_string(),
+            QuestionKind::Group => "group".to_string(),
+            QuestionKind::Other(kind) => kind,
+        }
+    }
+}
+
+///
+/// A Metaculus project, such as a tournament or question series, that a question belongs to.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Project {
+    /// The id of the project, as used by [QuestionQuery::project].
+    #[serde(default, deserialize_with = "lenient")]
+    pub id: u64,
+    /// The name of the project.
+    #[serde(default, deserialize_with = "lenient")]
+    pub name: String,
+}
+
 ///
 /// An aggregated overall prediction on a Metaculus question.
 ///
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum Prediction {
     /// Represents an Ambiguous resolution.
     AmbP,
+    ///
+    /// Represents the resolution of an annulled question, such as one whose premise turned out to
+    /// be false or the branch of a conditional pair that did not happen.
+    ///
+    Annulled,
     /// Represents a numeric prediction, either a probability (from 0.0 to 1.0) or continuous.
     NumP(f64),
     /// Represents a date prediction.
     DatP(NaiveDateTime),
+    ///
+    /// Represents a prediction on a group (or multiple-choice) question, as the probability of
+    /// each option, labelled and in the order Metaculus lists them.
+    ///
+    MultipleChoice(Vec<(String, f64)>),
+    /// Represents the resolution of a continuous question below its open lower bound.
+    BelowLower,
+    /// Represents the resolution of a continuous question above its open upper bound.
+    AboveUpper,
+}
+
+///
+/// The lower quartile, median and upper quartile of an aggregated prediction, in real units.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Quartiles {
+    /// The 25th percentile.
+    pub lower: Prediction,
+    /// The 50th percentile.
+    pub median: Prediction,
+    /// The 75th percentile.
+    pub upper: Prediction,
 }
 
 impl Prediction {
@@ -288,6 +1230,17 @@ impl Prediction {
             _ => None,
         }
     }
+
+    ///
+    /// Returns the labelled probability of each option if it is a group (or multiple-choice)
+    /// question, and `None` otherwise.
+    ///
+    pub fn get_if_multiple_choice(&self) -> Option<&[(String, f64)]> {
+        match self {
+            MultipleChoice(options) => Some(options),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -296,10 +1249,18 @@ enum PredictionTimeseriesPoint {
     NumericPTP {
         t: f64,
         community_prediction: f64,
+        #[serde(default)]
+        num_predictions: Option<u64>,
+        #[serde(default, deserialize_with = "lenient")]
+        distribution: Option<TimeseriesDistributionRepr>,
     },
     RangePTP {
         t: f64,
         community_prediction: RangeCommunityPrediction,
+        #[serde(default)]
+        num_predictions: Option<u64>,
+        #[serde(default, deserialize_with = "lenient")]
+        distribution: Option<TimeseriesDistributionRepr>,
     },
 }
 
@@ -310,18 +1271,71 @@ impl PredictionTimeseriesPoint {
             RangePTP { t, .. } => *t,
         }
     }
+
+    fn num_predictions(&self) -> Option<u64> {
+        match self {
+            NumericPTP {
+                num_predictions, ..
+            } => *num_predictions,
+            RangePTP {
+                num_predictions, ..
+            } => *num_predictions,
+        }
+    }
+
+    fn distribution(&self) -> Option<&TimeseriesDistributionRepr> {
+        match self {
+            NumericPTP { distribution, .. } => distribution.as_ref(),
+            RangePTP { distribution, .. } => distribution.as_ref(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct RangeCommunityPrediction {
+    q1: Option<f64>,
     q2: f64,
+    q3: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 struct MetaculusPrediction {
+    #[serde(default)]
+    full: Option<FullPrediction>,
     history: Vec<MetaculusPredictionTimeseriesPoint>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+struct CommunityPrediction {
+    full: Option<FullPrediction>,
+    #[serde(default)]
+    history: Vec<CommunityPredictionHistoryPoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct CommunityPredictionHistoryPoint {
+    t: f64,
+    x1: HistoryQuartiles,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct HistoryQuartiles {
+    q1: f64,
+    q2: f64,
+    q3: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum FullPrediction {
+    NumericFP(f64),
+    RangeFP {
+        y: Vec<f64>,
+        low: Option<f64>,
+        high: Option<f64>,
+    },
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 enum MetaculusPredictionTimeseriesPoint {
@@ -340,7 +1354,23 @@ impl MetaculusPredictionTimeseriesPoint {
 
 #[derive(Serialize, Deserialize, Clone)]
 struct RangeMetaculusPrediction {
+    q1: Option<f64>,
     q2: f64,
+    q3: Option<f64>,
+    low: Option<f64>,
+    high: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct MyPredictions {
+    predictions: Vec<MyPredictionPoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum MyPredictionPoint {
+    NumericMyPP { t: f64, x: f64 },
+    RangeMyPP { t: f64, d: LogisticMixture },
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -349,6 +1379,74 @@ struct QuestionPossibilities {
     question_type: String,
     scale: Option<RangeQuestionScale>,
     format: Option<String>,
+    #[serde(default, with = "open_bound")]
+    low: bool,
+    #[serde(default, with = "open_bound")]
+    high: bool,
+}
+
+///
+/// Metaculus marks an open bound of a continuous question's scale with `"tail"` and a closed bound
+/// with `false`.
+///
+mod open_bound {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(open: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        if *open {
+            serializer.serialize_str("tail")
+        } else {
+            serializer.serialize_bool(false)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+        let bound = Option::<serde_json::Value>::deserialize(deserializer)?;
+        Ok(bound.as_ref().and_then(|b| b.as_str()) == Some("tail"))
+    }
+}
+
+///
+/// Returns the last of the `points` (which must be in chronological order) at or before
+/// `timestamp`, using a binary search.
+///
+fn latest_by<T>(points: &[T], timestamp: f64, time: impl Fn(&T) -> f64) -> Option<&T> {
+    let after = points.partition_point(|point| time(point) <= timestamp);
+    points.get(after.checked_sub(1)?)
+}
+
+///
+/// (De)serializes the timestamps Metaculus returns, such as `2021-09-02T21:23:04.627935Z`, which
+/// may or may not have fractional seconds.
+///
+mod metaculus_time {
+    use chrono::{DateTime, NaiveDateTime};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<NaiveDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.collect_str(&time.format("%Y-%m-%dT%H:%M:%S%.fZ")),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDateTime>, D::Error> {
+        // A missing or malformed time is treated as unknown rather than failing the question.
+        let time = match Option::<Value>::deserialize(deserializer)? {
+            Some(Value::String(time)) => time,
+            _ => return Ok(None),
+        };
+        Ok(DateTime::parse_from_rfc3339(&time)
+            .map(|t| t.naive_utc())
+            .or_else(|_| NaiveDateTime::parse_from_str(&time, "%Y-%m-%dT%H:%M:%S%.f"))
+            .ok())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -358,10 +1456,14 @@ enum RangeQuestionScale {
         min: f64,
         max: f64,
         deriv_ratio: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        zero_point: Option<f64>,
     },
     DateRangeQuestionScale {
         min: String,
         max: String,
         deriv_ratio: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        zero_point: Option<f64>,
     },
 }
@@ -0,0 +1,793 @@
+//!
+//! Deserialization of the posts-based Metaculus API (`/api/posts/{id}/`), in which each post
+//! contains a single question, a group of questions, or a conditional pair. Posts are converted
+//! into the same [Question] model as the legacy API (`/api2/questions/{id}`), so the rest of the
+//! crate need not know which generation of the API a question came from.
+//!
+
+use crate::conditional::ConditionalQuestion;
+use crate::date_utils::DateUtils;
+use crate::error::from_value;
+use crate::scale::Scale;
+use crate::FullPrediction::{NumericFP, RangeFP};
+use crate::MetaculusPredictionTimeseriesPoint::{NumericMPTP, RangeMPTP};
+use crate::PredictionTimeseriesPoint::{NumericPTP, RangePTP};
+use crate::RangeQuestionScale::{DateRangeQuestionScale, NumericRangeQuestionScale};
+use crate::{
+    CommunityPrediction, CommunityPredictionHistoryPoint, FullPrediction, HistoryQuartiles,
+    MetaculusError, MetaculusPrediction, Project, PublicationStatus, Question, QuestionKind,
+    QuestionPossibilities, RangeCommunityPrediction, RangeMetaculusPrediction, RangeQuestionScale,
+    AMBIGUOUS_RESOLUTION, ANNULLED_RESOLUTION,
+};
+use chrono::{DateTime, NaiveDateTime, SecondsFormat};
+use serde::Deserialize;
+use serde_json::Value;
+
+///
+/// The normalised resolution used for questions that resolved below their lower bound. The legacy
+/// API reports where such resolutions lie beyond the scale, but the posts-based API does not, so
+/// this is just outside it.
+///
+const BELOW_LOWER_BOUND: f64 = -0.01;
+/// The normalised resolution used for questions that resolved above their upper bound.
+const ABOVE_UPPER_BOUND: f64 = 1.01;
+
+///
+/// The generation of the Metaculus API that a client requests questions from, set with
+/// [MetaculusBuilder::api_version](crate::MetaculusBuilder::api_version).
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ApiVersion {
+    /// The legacy API, which serves questions from `/api2/questions/{id}`.
+    Legacy,
+    /// The posts-based API, which serves questions from `/api/posts/{id}/`.
+    Posts,
+    ///
+    /// Requests questions from the legacy API, but accepts responses from either generation,
+    /// detecting which from the shape of the payload. This suits mirrors and saved responses.
+    ///
+    #[default]
+    Auto,
+}
+
+impl ApiVersion {
+    ///
+    /// Returns the generation of the API that `payload` came from: [ApiVersion::Posts] if it has
+    /// the `question`, `group_of_questions` or `conditional` of a post and none of the
+    /// `possibilities` of a legacy question, and [ApiVersion::Legacy] otherwise.
+    ///
+    pub fn detect(payload: &Value) -> ApiVersion {
+        let is_post = payload.get("possibilities").is_none()
+            && ["question", "group_of_questions", "conditional"]
+                .iter()
+                .any(|key| payload.get(key).is_some_and(|v| !v.is_null()));
+
+        if is_post {
+            ApiVersion::Posts
+        } else {
+            ApiVersion::Legacy
+        }
+    }
+
+    /// Resolves [ApiVersion::Auto] into the generation `payload` came from.
+    fn of(self, payload: &Value) -> ApiVersion {
+        match self {
+            ApiVersion::Auto => ApiVersion::detect(payload),
+            version => version,
+        }
+    }
+}
+
+/// Converts a question `payload` from the given generation of the API into a [Question].
+pub(crate) fn question_from_value(
+    payload: Value,
+    version: ApiVersion,
+) -> Result<Question, MetaculusError> {
+    match version.of(&payload) {
+        ApiVersion::Posts => from_value::<Post>(payload)?.into_question(),
+        _ => from_value(payload),
+    }
+}
+
+///
+/// Converts a conditional pair `payload` from the given generation of the API into a
+/// [ConditionalQuestion].
+///
+pub(crate) fn conditional_from_value(
+    payload: Value,
+    version: ApiVersion,
+) -> Result<ConditionalQuestion, MetaculusError> {
+    match version.of(&payload) {
+        ApiVersion::Posts => from_value::<Post>(payload)?.into_conditional(),
+        _ => from_value(payload),
+    }
+}
+
+#[derive(Deserialize)]
+struct Post {
+    id: u64,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    short_title: String,
+    #[serde(default)]
+    slug: String,
+    #[serde(default)]
+    author_username: String,
+    #[serde(default, with = "crate::metaculus_time")]
+    created_at: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    published_at: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    open_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    scheduled_close_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    actual_close_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    scheduled_resolve_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    actual_resolve_time: Option<NaiveDateTime>,
+    #[serde(default)]
+    curation_status: Option<String>,
+    #[serde(default)]
+    projects: PostProjects,
+    #[serde(default)]
+    comment_count: u64,
+    #[serde(default)]
+    vote: PostVote,
+    #[serde(default)]
+    forecasts_count: u64,
+    #[serde(default)]
+    question: Option<PostQuestion>,
+    #[serde(default)]
+    group_of_questions: Option<PostGroup>,
+    #[serde(default)]
+    conditional: Option<PostConditional>,
+}
+
+#[derive(Deserialize, Default)]
+struct PostProjects {
+    #[serde(default)]
+    category: Vec<PostProject>,
+    #[serde(default)]
+    tournament: Vec<PostProject>,
+    #[serde(default)]
+    question_series: Vec<PostProject>,
+    #[serde(default)]
+    default_project: Option<PostProject>,
+}
+
+#[derive(Deserialize)]
+struct PostProject {
+    id: u64,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    slug: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct PostVote {
+    #[serde(default)]
+    score: i64,
+}
+
+#[derive(Deserialize)]
+struct PostGroup {
+    #[serde(default)]
+    description: String,
+    questions: Vec<PostQuestion>,
+}
+
+#[derive(Deserialize)]
+struct PostConditional {
+    condition: PostQuestion,
+    condition_child: PostQuestion,
+    question_yes: PostQuestion,
+    question_no: PostQuestion,
+}
+
+#[derive(Deserialize)]
+struct PostQuestion {
+    #[serde(default)]
+    id: u64,
+    #[serde(default)]
+    title: String,
+    #[serde(rename = "type")]
+    question_type: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    options: Vec<String>,
+    #[serde(default)]
+    scaling: PostScaling,
+    #[serde(default)]
+    open_lower_bound: Option<bool>,
+    #[serde(default)]
+    open_upper_bound: Option<bool>,
+    #[serde(default, with = "crate::metaculus_time")]
+    open_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    scheduled_close_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    actual_close_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    scheduled_resolve_time: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::metaculus_time")]
+    actual_resolve_time: Option<NaiveDateTime>,
+    #[serde(default)]
+    resolution: Option<Value>,
+    #[serde(default)]
+    aggregations: PostAggregations,
+}
+
+#[derive(Deserialize, Default)]
+struct PostScaling {
+    #[serde(default)]
+    range_min: Option<f64>,
+    #[serde(default)]
+    range_max: Option<f64>,
+    #[serde(default)]
+    zero_point: Option<f64>,
+}
+
+#[derive(Deserialize, Default)]
+struct PostAggregations {
+    #[serde(default)]
+    recency_weighted: Option<Aggregation>,
+    #[serde(default)]
+    unweighted: Option<Aggregation>,
+    #[serde(default)]
+    single_aggregation: Option<Aggregation>,
+    #[serde(default)]
+    metaculus_prediction: Option<Aggregation>,
+}
+
+impl PostAggregations {
+    ///
+    /// Returns the aggregation shown as the community prediction: the recency-weighted median,
+    /// falling back on the unweighted median and then on the single aggregation used by some
+    /// tournaments.
+    ///
+    fn community(&self) -> Option<&Aggregation> {
+        [
+            &self.recency_weighted,
+            &self.unweighted,
+            &self.single_aggregation,
+        ]
+        .into_iter()
+        .flatten()
+        .find(|aggregation| !aggregation.is_empty())
+    }
+
+    fn metaculus(&self) -> Option<&Aggregation> {
+        self.metaculus_prediction
+            .as_ref()
+            .filter(|aggregation| !aggregation.is_empty())
+    }
+}
+
+#[derive(Deserialize)]
+struct Aggregation {
+    #[serde(default)]
+    history: Vec<AggregateForecast>,
+    #[serde(default)]
+    latest: Option<AggregateForecast>,
+}
+
+impl Aggregation {
+    fn is_empty(&self) -> bool {
+        self.history.is_empty() && self.latest.is_none()
+    }
+
+    ///
+    /// Returns the forecasts in chronological order, ending with the latest one. The latest
+    /// forecast replaces the last in the history if they started at the same time, since only the
+    /// latest includes the full distribution.
+    ///
+    fn forecasts(&self) -> Vec<&AggregateForecast> {
+        let mut forecasts: Vec<&AggregateForecast> = self.history.iter().collect();
+        if let Some(latest) = &self.latest {
+            let start = latest.start_time.timestamp();
+            if forecasts.last().and_then(|f| f.start_time.timestamp()) == start {
+                forecasts.pop();
+            }
+            if forecasts.last().and_then(|f| f.start_time.timestamp()) < start {
+                forecasts.push(latest);
+            }
+        }
+        forecasts
+    }
+}
+
+#[derive(Deserialize)]
+struct AggregateForecast {
+    start_time: PostTime,
+    #[serde(default)]
+    centers: Option<Vec<f64>>,
+    #[serde(default)]
+    interval_lower_bounds: Option<Vec<f64>>,
+    #[serde(default)]
+    interval_upper_bounds: Option<Vec<f64>>,
+    #[serde(default)]
+    forecast_values: Option<Vec<f64>>,
+    #[serde(default)]
+    forecaster_count: Option<u64>,
+}
+
+impl AggregateForecast {
+    fn center(&self, i: usize) -> Option<f64> {
+        self.centers.as_ref()?.get(i).copied()
+    }
+
+    fn lower(&self, i: usize) -> Option<f64> {
+        self.interval_lower_bounds.as_ref()?.get(i).copied()
+    }
+
+    fn upper(&self, i: usize) -> Option<f64> {
+        self.interval_upper_bounds.as_ref()?.get(i).copied()
+    }
+
+    /// The cumulative distribution over the normalised scale of a continuous question.
+    fn cdf(&self) -> Option<&[f64]> {
+        self.forecast_values
+            .as_deref()
+            .filter(|values| values.len() > 2)
+    }
+}
+
+/// A time in the posts-based API, which is either a Unix timestamp or an RFC 3339 string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PostTime {
+    Seconds(f64),
+    Text(String),
+}
+
+impl PostTime {
+    fn timestamp(&self) -> Option<f64> {
+        match self {
+            PostTime::Seconds(seconds) => Some(*seconds),
+            PostTime::Text(text) => {
+                let time = DateTime::parse_from_rfc3339(text).ok()?;
+                Some(time.timestamp_micros() as f64 / 1e6)
+            }
+        }
+    }
+}
+
+impl Post {
+    fn into_question(self) -> Result<Question, MetaculusError> {
+        if let Some(question) = &self.question {
+            let mut converted = self.convert(question);
+            converted.id = self.id;
+            converted.title.clone_from(&self.title);
+            converted.title_short = self.short_title();
+            converted.number_of_predictions = self.forecasts_count;
+            return Ok(converted);
+        }
+
+        if let Some(group) = &self.group_of_questions {
+            let mut converted = self.metadata();
+            converted.kind = QuestionKind::Group;
+            converted.description_html.clone_from(&group.description);
+            converted.possibilities = possibilities("group", None, false, false);
+            converted.sub_questions = group.questions.iter().map(|q| self.convert(q)).collect();
+            return Ok(converted);
+        }
+
+        Err(self.wrong_kind("question or group_of_questions"))
+    }
+
+    fn into_conditional(self) -> Result<ConditionalQuestion, MetaculusError> {
+        let conditional = match &self.conditional {
+            Some(conditional) => conditional,
+            None => return Err(self.wrong_kind("conditional")),
+        };
+
+        Ok(ConditionalQuestion {
+            id: self.id,
+            condition: self.convert(&conditional.condition),
+            outcome: self.convert(&conditional.condition_child),
+            if_yes: self.convert(&conditional.question_yes),
+            if_no: self.convert(&conditional.question_no),
+        })
+    }
+
+    fn wrong_kind(&self, expected: &str) -> MetaculusError {
+        MetaculusError::Deserialize {
+            path: expected.to_string(),
+            message: format!("post {} does not contain a {}", self.id, expected),
+        }
+    }
+
+    fn short_title(&self) -> String {
+        if self.short_title.is_empty() {
+            self.title.clone()
+        } else {
+            self.short_title.clone()
+        }
+    }
+
+    /// Returns a question with the metadata of the post, and no predictions.
+    fn metadata(&self) -> Question {
+        let mut projects: Vec<Project> = Vec::new();
+        for project in self
+            .projects
+            .tournament
+            .iter()
+            .chain(&self.projects.question_series)
+            .chain(&self.projects.default_project)
+        {
+            if !projects.iter().any(|p| p.id == project.id) {
+                projects.push(Project {
+                    id: project.id,
+                    name: project.name.clone(),
+                });
+            }
+        }
+
+        Question {
+            id: self.id,
+            title: self.title.clone(),
+            title_short: self.short_title(),
+            url: String::new(),
+            page_url: if self.slug.is_empty() {
+                format!("/questions/{}/", self.id)
+            } else {
+                format!("/questions/{}/{}/", self.id, self.slug)
+            },
+            author_name: self.author_username.clone(),
+            created_time: self.created_at,
+            publish_time: self.open_time.or(self.published_at),
+            close_time: self.actual_close_time.or(self.scheduled_close_time),
+            resolve_time: self.actual_resolve_time.or(self.scheduled_resolve_time),
+            status: match self.curation_status.as_deref() {
+                Some("approved") => PublicationStatus::Approved,
+                Some("pending") => PublicationStatus::Pending,
+                Some("draft") => PublicationStatus::Draft,
+                status => PublicationStatus::Other(status.unwrap_or_default().to_string()),
+            },
+            kind: QuestionKind::Forecast,
+            categories: self
+                .projects
+                .category
+                .iter()
+                .map(|c| c.slug.clone().unwrap_or_else(|| c.name.clone()))
+                .collect(),
+            projects,
+            comment_count: self.comment_count,
+            votes: self.vote.score,
+            number_of_predictions: 0,
+            activity: 0.0,
+            description_html: String::new(),
+            sub_questions: Vec::new(),
+            sub_question_label: None,
+            prediction_timeseries: None,
+            metaculus_prediction: None,
+            resolution: None,
+            possibilities: possibilities("", None, false, false),
+            my_predictions: None,
+            community_prediction: None,
+        }
+    }
+
+    /// Converts one of the questions in the post, with the metadata of the post.
+    fn convert(&self, question: &PostQuestion) -> Question {
+        let mut converted = self.metadata();
+        converted.id = question.id;
+        converted.title.clone_from(&question.title);
+        converted.title_short.clone_from(&question.title);
+        converted.sub_question_label.clone_from(&question.label);
+        converted.description_html.clone_from(&question.description);
+        converted.publish_time = question.open_time.or(converted.publish_time);
+        converted.close_time = question
+            .actual_close_time
+            .or(question.scheduled_close_time)
+            .or(converted.close_time);
+        converted.resolve_time = question
+            .actual_resolve_time
+            .or(question.scheduled_resolve_time)
+            .or(converted.resolve_time);
+
+        let resolution = question.resolution_text();
+        match question.question_type.as_str() {
+            "binary" => {
+                converted.possibilities = possibilities("binary", None, false, false);
+                converted.resolution = resolution.as_deref().and_then(binary_resolution);
+                converted.set_binary_aggregations(&question.aggregations, 0);
+            }
+            "numeric" | "date" | "discrete" => {
+                converted.possibilities = possibilities(
+                    "continuous",
+                    question.range_scale(),
+                    question.open_lower_bound.unwrap_or(false),
+                    question.open_upper_bound.unwrap_or(false),
+                );
+                converted.possibilities.format = Some(if question.question_type == "date" {
+                    "date".to_string()
+                } else {
+                    "num".to_string()
+                });
+                converted.resolution = resolution
+                    .as_deref()
+                    .and_then(|r| continuous_resolution(r, converted.scale()?));
+                converted.set_continuous_aggregations(&question.aggregations);
+            }
+            "multiple_choice" => {
+                converted.possibilities = possibilities("multiple_choice", None, false, false);
+                converted.sub_questions = question
+                    .options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, option)| {
+                        let mut sub_question = converted.clone();
+                        sub_question.id = option_id(question.id, option);
+                        sub_question.possibilities = possibilities("binary", None, false, false);
+                        sub_question.title_short.clone_from(option);
+                        sub_question.sub_question_label = Some(option.clone());
+                        sub_question.resolution = resolution.as_deref().and_then(|r| {
+                            binary_resolution(r).or(Some(if r == option { 1.0 } else { 0.0 }))
+                        });
+                        sub_question.set_binary_aggregations(&question.aggregations, i);
+                        sub_question
+                    })
+                    .collect();
+            }
+            question_type => {
+                converted.possibilities = possibilities(question_type, None, false, false);
+            }
+        }
+
+        converted
+    }
+}
+
+///
+/// Returns the id given to the `option` of the multiple-choice question `id`, which has no id of
+/// its own. It is derived from both, so that it stays the same between requests, and has the
+/// highest bit set so that it cannot clash with the id of a real question.
+///
+fn option_id(id: u64, option: &str) -> u64 {
+    // FNV-1a, which unlike the standard library's hasher is the same on every release.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in id.to_le_bytes().iter().chain(option.as_bytes()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash | 1 << 63
+}
+
+impl PostQuestion {
+    /// Returns the resolution as text, whether it is given as a string or a number.
+    fn resolution_text(&self) -> Option<String> {
+        match self.resolution.as_ref()? {
+            Value::String(text) if !text.is_empty() => Some(text.clone()),
+            Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the scale in the form used by the legacy API, where a logarithmic scale is marked by
+    /// the ratio of the derivatives at its ends, which is that of the distances of the maximum and
+    /// minimum from the zero point.
+    ///
+    fn range_scale(&self) -> Option<RangeQuestionScale> {
+        let min = self.scaling.range_min?;
+        let max = self.scaling.range_max?;
+        let zero_point = self.scaling.zero_point;
+        let deriv_ratio = zero_point.map_or(1.0, |z| (max - z) / (min - z));
+
+        if self.question_type == "date" {
+            let date = |timestamp: f64| {
+                Some(
+                    DateTime::from_timestamp(timestamp as i64, 0)?
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                )
+            };
+            Some(DateRangeQuestionScale {
+                min: date(min)?,
+                max: date(max)?,
+                deriv_ratio,
+                zero_point,
+            })
+        } else {
+            Some(NumericRangeQuestionScale {
+                min,
+                max,
+                deriv_ratio,
+                zero_point,
+            })
+        }
+    }
+}
+
+impl Question {
+    ///
+    /// Fills in the community and Metaculus predictions of a binary question from the probability
+    /// of the `option`th outcome in each aggregation (always `0` except for multiple choice).
+    ///
+    fn set_binary_aggregations(&mut self, aggregations: &PostAggregations, option: usize) {
+        if let Some(community) = aggregations.community() {
+            let forecasts = community.forecasts();
+            self.prediction_timeseries = Some(
+                forecasts
+                    .iter()
+                    .filter_map(|f| {
+                        Some(NumericPTP {
+                            t: f.start_time.timestamp()?,
+                            community_prediction: f.center(option)?,
+                            num_predictions: f.forecaster_count,
+                            distribution: None,
+                        })
+                    })
+                    .collect(),
+            );
+            self.community_prediction = Some(CommunityPrediction {
+                full: forecasts
+                    .last()
+                    .and_then(|f| f.center(option))
+                    .map(NumericFP),
+                history: forecasts
+                    .iter()
+                    .filter_map(|f| {
+                        Some(CommunityPredictionHistoryPoint {
+                            t: f.start_time.timestamp()?,
+                            x1: HistoryQuartiles {
+                                q1: f.lower(option)?,
+                                q2: f.center(option)?,
+                                q3: f.upper(option)?,
+                            },
+                        })
+                    })
+                    .collect(),
+            });
+        }
+
+        if let Some(metaculus) = aggregations.metaculus() {
+            let forecasts = metaculus.forecasts();
+            self.metaculus_prediction = Some(MetaculusPrediction {
+                full: forecasts
+                    .last()
+                    .and_then(|f| f.center(option))
+                    .map(NumericFP),
+                history: forecasts
+                    .iter()
+                    .filter_map(|f| {
+                        Some(NumericMPTP {
+                            t: f.start_time.timestamp()?,
+                            x: f.center(option)?,
+                        })
+                    })
+                    .collect(),
+            });
+        }
+    }
+
+    ///
+    /// Fills in the community and Metaculus predictions of a continuous question, whose centres
+    /// and interval bounds are the medians and quartiles on the normalised scale.
+    ///
+    fn set_continuous_aggregations(&mut self, aggregations: &PostAggregations) {
+        if let Some(community) = aggregations.community() {
+            let forecasts = community.forecasts();
+            self.prediction_timeseries = Some(
+                forecasts
+                    .iter()
+                    .filter_map(|f| {
+                        Some(RangePTP {
+                            t: f.start_time.timestamp()?,
+                            community_prediction: RangeCommunityPrediction {
+                                q1: f.lower(0),
+                                q2: f.center(0)?,
+                                q3: f.upper(0),
+                            },
+                            num_predictions: f.forecaster_count,
+                            distribution: None,
+                        })
+                    })
+                    .collect(),
+            );
+            self.community_prediction = Some(CommunityPrediction {
+                full: forecasts.last().and_then(|f| full_distribution(f.cdf()?)),
+                history: Vec::new(),
+            });
+        }
+
+        if let Some(metaculus) = aggregations.metaculus() {
+            let forecasts = metaculus.forecasts();
+            self.metaculus_prediction = Some(MetaculusPrediction {
+                full: forecasts.last().and_then(|f| full_distribution(f.cdf()?)),
+                history: forecasts
+                    .iter()
+                    .filter_map(|f| {
+                        Some(RangeMPTP {
+                            t: f.start_time.timestamp()?,
+                            x: RangeMetaculusPrediction {
+                                q1: f.lower(0),
+                                q2: f.center(0)?,
+                                q3: f.upper(0),
+                                low: f.cdf().and_then(|cdf| cdf.first().copied()),
+                                high: f.cdf().and_then(|cdf| cdf.last().copied()),
+                            },
+                        })
+                    })
+                    .collect(),
+            });
+        }
+    }
+}
+
+fn possibilities(
+    question_type: &str,
+    scale: Option<RangeQuestionScale>,
+    low: bool,
+    high: bool,
+) -> QuestionPossibilities {
+    QuestionPossibilities {
+        question_type: question_type.to_string(),
+        scale,
+        format: None,
+        low,
+        high,
+    }
+}
+
+///
+/// Converts a cumulative distribution at evenly spaced points on the normalised scale into the
+/// density and bound probabilities reported by the legacy API.
+///
+fn full_distribution(cdf: &[f64]) -> Option<FullPrediction> {
+    let n = cdf.len() - 1;
+    let step = 1.0 / n as f64;
+    let y = (0..=n)
+        .map(|i| {
+            let (before, after) = (i.saturating_sub(1), (i + 1).min(n));
+            (cdf[after] - cdf[before]) / ((after - before) as f64 * step)
+        })
+        .collect();
+
+    Some(RangeFP {
+        y,
+        low: Some(*cdf.first()?),
+        high: Some(*cdf.last()?),
+    })
+}
+
+///
+/// Converts the resolution of a binary question into the codes used by the legacy API: `1.0` for
+/// yes, `0.0` for no, and the codes for ambiguous and annulled questions.
+///
+fn binary_resolution(resolution: &str) -> Option<f64> {
+    match resolution {
+        "yes" => Some(1.0),
+        "no" => Some(0.0),
+        "ambiguous" => Some(AMBIGUOUS_RESOLUTION),
+        "annulled" => Some(ANNULLED_RESOLUTION),
+        _ => None,
+    }
+}
+
+///
+/// Converts the resolution of a continuous question (a number, or a date for date questions) into
+/// its position on the normalised scale, as reported by the legacy API.
+///
+fn continuous_resolution(resolution: &str, scale: Scale) -> Option<f64> {
+    match resolution {
+        "ambiguous" | "annulled" => binary_resolution(resolution),
+        "below_lower_bound" => Some(BELOW_LOWER_BOUND),
+        "above_upper_bound" => Some(ABOVE_UPPER_BOUND),
+        _ => {
+            let value = if scale.dates {
+                NaiveDateTime::date_to_timestamp(resolution)?
+            } else {
+                resolution.parse().ok()?
+            };
+            Some(scale.normalise(value))
+        }
+    }
+}
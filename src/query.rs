@@ -0,0 +1,218 @@
+//!
+//! A module for searching and listing questions through the `/api2/questions/` endpoint (or
+//! `/api/posts/`, for clients using [ApiVersion::Posts]).
+//!
+
+use crate::{posts, ApiVersion, Metaculus, MetaculusError, Question};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+///
+/// Filters for listing questions with [Metaculus::list_questions]. Every filter is optional, and an
+/// empty query lists every question.
+///
+/// # Example
+///
+/// ``` rust
+/// use metaculustetra::query::QuestionQuery;
+///
+/// // Open questions in the Nuclear Risk Tournament, most active first
+/// let query = QuestionQuery::new()
+///     .project("1007")
+///     .status("open")
+///     .order_by("-activity");
+/// assert_eq!(
+///     query.to_query_string(),
+///     "project=1007&status=open&order_by=-activity"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QuestionQuery {
+    search: Option<String>,
+    status: Option<String>,
+    project: Option<String>,
+    categories: Vec<String>,
+    order_by: Option<String>,
+    limit: Option<u32>,
+}
+
+impl QuestionQuery {
+    /// Makes a new, empty `QuestionQuery`.
+    pub fn new() -> QuestionQuery {
+        QuestionQuery::default()
+    }
+
+    /// Only lists questions matching the search `terms`.
+    pub fn search(mut self, terms: &str) -> QuestionQuery {
+        self.search = Some(terms.to_string());
+        self
+    }
+
+    /// Only lists questions with the given status, such as `open`, `closed`, or `resolved`.
+    pub fn status(mut self, status: &str) -> QuestionQuery {
+        self.status = Some(status.to_string());
+        self
+    }
+
+    /// Only lists questions in the project (such as a tournament) with id `project`.
+    pub fn project(mut self, project: &str) -> QuestionQuery {
+        self.project = Some(project.to_string());
+        self
+    }
+
+    ///
+    /// Only lists questions in the given category, such as `computing--ai`. Can be called more than
+    /// once to filter by several categories.
+    ///
+    pub fn category(mut self, category: &str) -> QuestionQuery {
+        self.categories.push(category.to_string());
+        self
+    }
+
+    ///
+    /// Orders the questions by the given field, such as `activity` or `-publish_time` (prefixed
+    /// with `-` for descending order).
+    ///
+    pub fn order_by(mut self, order_by: &str) -> QuestionQuery {
+        self.order_by = Some(order_by.to_string());
+        self
+    }
+
+    /// Sets the number of questions retrieved with each request.
+    pub fn page_size(mut self, limit: u32) -> QuestionQuery {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns the URL-encoded query string for the filters that have been set.
+    pub fn to_query_string(&self) -> String {
+        self.to_query_string_for(ApiVersion::Legacy)
+    }
+
+    ///
+    /// Returns the URL-encoded query string for the filters that have been set, using the
+    /// parameter names of the given generation of the API. The posts-based API names the filters
+    /// differently (`statuses`, `tournaments`, and one `categories` parameter per category), and
+    /// orders by different fields, so the common legacy orderings are translated.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use metaculustetra::query::QuestionQuery;
+    /// use metaculustetra::ApiVersion;
+    ///
+    /// let query = QuestionQuery::new().project("1007").status("open").order_by("-activity");
+    /// assert_eq!(
+    ///     query.to_query_string_for(ApiVersion::Posts),
+    ///     "tournaments=1007&statuses=open&order_by=-hotness"
+    /// );
+    /// ```
+    pub fn to_query_string_for(&self, version: ApiVersion) -> String {
+        let posts_api = version == ApiVersion::Posts;
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(search) = &self.search {
+            serializer.append_pair("search", search);
+        }
+        if let Some(project) = &self.project {
+            serializer.append_pair(if posts_api { "tournaments" } else { "project" }, project);
+        }
+        if let Some(status) = &self.status {
+            serializer.append_pair(if posts_api { "statuses" } else { "status" }, status);
+        }
+        if posts_api {
+            for category in &self.categories {
+                serializer.append_pair("categories", category);
+            }
+        } else if !self.categories.is_empty() {
+            serializer.append_pair("categories", &self.categories.join(","));
+        }
+        if let Some(order_by) = &self.order_by {
+            if posts_api {
+                serializer.append_pair("order_by", &posts_order_by(order_by));
+            } else {
+                serializer.append_pair("order_by", order_by);
+            }
+        }
+        if let Some(limit) = self.limit {
+            serializer.append_pair("limit", &limit.to_string());
+        }
+        serializer.finish()
+    }
+}
+
+/// Translates a legacy `order_by` field into the posts-based API's name for it, if it has one.
+fn posts_order_by(order_by: &str) -> String {
+    let (direction, field) = match order_by.strip_prefix('-') {
+        Some(field) => ("-", field),
+        None => ("", order_by),
+    };
+    let field = match field {
+        "activity" => "hotness",
+        "publish_time" => "published_at",
+        "close_time" => "scheduled_close_time",
+        "resolve_time" => "scheduled_resolve_time",
+        "votes" => "vote_score",
+        "number_of_predictions" => "forecasts_count",
+        field => field,
+    };
+    format!("{}{}", direction, field)
+}
+
+///
+/// A lazy iterator over the questions matching a [QuestionQuery], created with
+/// [Metaculus::list_questions]. Each page of results is only requested once the previous page has
+/// been used up.
+///
+/// If a page cannot be retrieved, the iterator returns the [MetaculusError] and then ends.
+///
+pub struct QuestionIter<'a> {
+    metaculus: &'a Metaculus,
+    next_url: Option<String>,
+    questions: VecDeque<Value>,
+}
+
+///
+/// A page of search results. The results are parsed once they are used, since they may come from
+/// either generation of the API.
+///
+#[derive(Deserialize)]
+pub(crate) struct QuestionPage {
+    pub(crate) next: Option<String>,
+    pub(crate) results: Vec<Value>,
+}
+
+impl<'a> QuestionIter<'a> {
+    pub(crate) fn new(metaculus: &'a Metaculus, url: String) -> QuestionIter<'a> {
+        QuestionIter {
+            metaculus,
+            next_url: Some(url),
+            questions: VecDeque::new(),
+        }
+    }
+}
+
+impl Iterator for QuestionIter<'_> {
+    type Item = Result<Question, MetaculusError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.questions.is_empty() {
+            let url = self.next_url.take()?;
+            match self.metaculus.get_json::<QuestionPage>(&url, &url) {
+                Ok(page) => {
+                    self.next_url = page
+                        .next
+                        .and_then(|next| self.metaculus.config.next_page_url(&next));
+                    self.questions.extend(page.results);
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        let payload = self.questions.pop_front()?;
+        Some(posts::question_from_value(
+            payload,
+            self.metaculus.config.api_version,
+        ))
+    }
+}
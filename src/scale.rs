@@ -0,0 +1,80 @@
+use crate::Prediction;
+use crate::Prediction::{AboveUpper, BelowLower, DatP, NumP};
+use chrono::DateTime;
+
+///
+/// The scale of a continuous question, which converts between Metaculus' normalised values (where
+/// `0.0` is the minimum and `1.0` is the maximum) and real units. Dates are handled as numbers of
+/// seconds since midnight, January 1st, 1970.
+///
+/// Logarithmic scales are logarithmic in the distance from `zero_point`, which is `0.0` for every
+/// question in the legacy API.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Scale {
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+    pub(crate) logarithmic: bool,
+    pub(crate) dates: bool,
+    pub(crate) zero_point: f64,
+}
+
+impl Scale {
+    /// Converts a normalised value into a raw number (or timestamp for dates).
+    pub(crate) fn denormalise(&self, normalised: f64) -> f64 {
+        if self.logarithmic {
+            self.ratio().powf(normalised) * (self.min - self.zero_point) + self.zero_point
+        } else {
+            normalised * (self.max - self.min) + self.min
+        }
+    }
+
+    /// Converts a raw number (or timestamp for dates) into a normalised value.
+    pub(crate) fn normalise(&self, value: f64) -> f64 {
+        if self.logarithmic {
+            ((value - self.zero_point) / (self.min - self.zero_point)).ln() / self.ratio().ln()
+        } else {
+            (value - self.min) / (self.max - self.min)
+        }
+    }
+
+    ///
+    /// Returns the rate at which the raw value changes with the normalised value at `normalised`,
+    /// used to convert densities on the normalised scale into densities per real unit.
+    ///
+    pub(crate) fn derivative(&self, normalised: f64) -> f64 {
+        if self.logarithmic {
+            (self.denormalise(normalised) - self.zero_point) * self.ratio().ln()
+        } else {
+            self.max - self.min
+        }
+    }
+
+    /// Returns the ratio of the distances of the maximum and minimum from the zero point.
+    fn ratio(&self) -> f64 {
+        (self.max - self.zero_point) / (self.min - self.zero_point)
+    }
+
+    /// Converts a raw number (or timestamp for dates) into a [Prediction] in real units.
+    pub(crate) fn prediction_at(&self, value: f64) -> Option<Prediction> {
+        if self.dates {
+            Some(DatP(DateTime::from_timestamp(value as i64, 0)?.naive_utc()))
+        } else {
+            Some(NumP(value))
+        }
+    }
+
+    ///
+    /// Converts a [Prediction] in real units into a raw number (or timestamp for dates), if it is
+    /// of the right type for the scale. Resolutions beyond the bounds are clamped to the bounds.
+    ///
+    pub(crate) fn value_of(&self, prediction: &Prediction) -> Option<f64> {
+        match (self.dates, prediction) {
+            (false, NumP(p)) => Some(*p),
+            (true, DatP(p)) => Some(p.and_utc().timestamp() as f64),
+            (_, BelowLower) => Some(self.min),
+            (_, AboveUpper) => Some(self.max),
+            _ => None,
+        }
+    }
+}
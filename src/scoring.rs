@@ -0,0 +1,385 @@
+//!
+//! A module for scoring the community and Metaculus predictions on resolved questions against their
+//! resolutions.
+//!
+//! Binary questions are scored with the Brier score and the log score, and continuous questions
+//! with the log score and the continuous ranked probability score (CRPS). Continuous questions are
+//! scored on their normalised scale (where `0.0` is the lower bound and `1.0` is the upper bound),
+//! so that scores can be compared between questions with different units.
+//!
+//! # Example
+//! ```rust
+//! use metaculustetra::scoring::{final_scores, time_averaged_scores, Aggregate, Scores};
+//! use std::fs::File;
+//! use std::io::BufReader;
+//! use metaculustetra::Question;
+//!
+//! let file = File::open("tests/resolved_probability_example.json").unwrap();
+//! let question: Question = serde_json::from_reader(BufReader::new(file)).unwrap();
+//!
+//! // The community predicted 99% on a question that resolved yes
+//! match final_scores(&question, Aggregate::Community).unwrap() {
+//!     Scores::Binary { brier, log } => {
+//!         assert!((brier - 0.0001).abs() < 1e-9);
+//!         assert!((log - 0.99_f64.ln()).abs() < 1e-9);
+//!     }
+//!     Scores::Continuous { .. } => unreachable!(),
+//! }
+//! let averaged = time_averaged_scores(&question, Aggregate::Community).unwrap();
+//! ```
+
+use crate::distribution::{
+    ContinuousDistribution, LogisticComponent, LogisticDistribution, TimeseriesDistribution,
+};
+use crate::MetaculusPredictionTimeseriesPoint::{NumericMPTP, RangeMPTP};
+use crate::PredictionTimeseriesPoint::{NumericPTP, RangePTP};
+use crate::{
+    latest_by, AmbP, Annulled, MetaculusPredictionTimeseriesPoint, PredictionTimeseriesPoint,
+    Question,
+};
+use chrono::NaiveDateTime;
+
+/// The number of intervals used to integrate the CRPS over the normalised scale.
+const CRPS_STEPS: usize = 1000;
+
+/// The aggregate predictions on a question that can be scored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    /// The community prediction.
+    Community,
+    /// The Metaculus prediction.
+    Metaculus,
+}
+
+///
+/// The scores of a prediction against the resolution of a question. Log scores use the natural
+/// logarithm, so higher is better; Brier scores and CRPS are errors, so lower is better.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scores {
+    /// The scores of a probability on a binary question.
+    Binary {
+        /// The squared difference between the probability and the outcome (`0.0` or `1.0`).
+        brier: f64,
+        /// The natural logarithm of the probability assigned to the outcome.
+        log: f64,
+    },
+    /// The scores of a distribution on a continuous question.
+    Continuous {
+        ///
+        /// The natural logarithm of the density at the resolution on the normalised scale, or of
+        /// the probability beyond the bound if the question resolved outside its scale.
+        ///
+        log: f64,
+        /// The continuous ranked probability score over the normalised scale.
+        crps: f64,
+    },
+}
+
+impl Scores {
+    /// Returns the log score, which is available for every kind of question.
+    pub fn log(&self) -> f64 {
+        match self {
+            Scores::Binary { log, .. } => *log,
+            Scores::Continuous { log, .. } => *log,
+        }
+    }
+
+    fn components(&self) -> [f64; 2] {
+        match *self {
+            Scores::Binary { brier, log } => [brier, log],
+            Scores::Continuous { log, crps } => [log, crps],
+        }
+    }
+
+    fn with_components(&self, [a, b]: [f64; 2]) -> Scores {
+        match self {
+            Scores::Binary { .. } => Scores::Binary { brier: a, log: b },
+            Scores::Continuous { .. } => Scores::Continuous { log: a, crps: b },
+        }
+    }
+}
+
+///
+/// Scores the final `aggregate` prediction on the question against its resolution, or returns
+/// `None` if the question has not resolved, resolved ambiguously, was annulled, or has no such
+/// prediction.
+///
+/// The final prediction is the one in place when the question closed or resolved, whichever came
+/// first. For continuous questions, this is the full distribution reported by Metaculus if it was
+/// last updated by then, and otherwise the distribution at that time approximated as for
+/// [time_averaged_scores].
+///
+pub fn final_scores(question: &Question, aggregate: Aggregate) -> Option<Scores> {
+    let outcome = Outcome::of(question)?;
+    let end = window_end(question)?;
+    match outcome {
+        Outcome::Binary(outcome) => {
+            let prediction = match aggregate {
+                Aggregate::Community => question.get_community_prediction_before(end)?,
+                Aggregate::Metaculus => question.get_metaculus_prediction_before(end)?,
+            };
+            Some(binary_scores(prediction.get_if_numeric()?, outcome))
+        }
+        Outcome::Continuous(resolution) => {
+            let end = end.and_utc().timestamp() as f64;
+            // The full distribution is only reported for the latest prediction, so it can only be
+            // used if nothing was predicted after the window closed.
+            match aggregate {
+                Aggregate::Community => {
+                    let points = question
+                        .prediction_timeseries
+                        .as_deref()
+                        .unwrap_or_default();
+                    match points.last() {
+                        Some(last) if last.timestamp() > end => score_community_point(
+                            question,
+                            latest_by(points, end, PredictionTimeseriesPoint::timestamp)?,
+                            &outcome,
+                        ),
+                        _ => Some(continuous_scores(
+                            &question.get_community_distribution()?,
+                            resolution,
+                        )),
+                    }
+                }
+                Aggregate::Metaculus => {
+                    let points = &question.metaculus_prediction.as_ref()?.history;
+                    match points.last() {
+                        Some(last) if last.timestamp() > end => score_metaculus_point(
+                            question,
+                            latest_by(points, end, MetaculusPredictionTimeseriesPoint::timestamp)?,
+                            &outcome,
+                        ),
+                        _ => Some(continuous_scores(
+                            &question.get_metaculus_distribution()?,
+                            resolution,
+                        )),
+                    }
+                }
+            }
+        }
+    }
+}
+
+///
+/// Scores the `aggregate` prediction on the question against its resolution, averaged over the
+/// time from the first prediction until the question closed or resolved (whichever came first),
+/// weighting each prediction by how long it stood. Returns `None` if the question has not
+/// resolved, resolved ambiguously, was annulled, or has no such predictions.
+///
+/// Continuous community predictions are taken from the logistic distributions recorded in the
+/// prediction timeseries. The history of the continuous Metaculus prediction only records its
+/// quartiles, so each point is approximated by the logistic distribution with the same median and
+/// interquartile range.
+///
+pub fn time_averaged_scores(question: &Question, aggregate: Aggregate) -> Option<Scores> {
+    let outcome = Outcome::of(question)?;
+    let start = question
+        .publish_time
+        .map_or(f64::NEG_INFINITY, |t| t.and_utc().timestamp() as f64);
+    let end = window_end(question)?.and_utc().timestamp() as f64;
+
+    let points: Vec<(f64, Option<Scores>)> = match aggregate {
+        Aggregate::Community => question
+            .prediction_timeseries
+            .as_ref()?
+            .iter()
+            .map(|point| {
+                (
+                    point.timestamp(),
+                    score_community_point(question, point, &outcome),
+                )
+            })
+            .collect(),
+        Aggregate::Metaculus => question
+            .metaculus_prediction
+            .as_ref()?
+            .history
+            .iter()
+            .map(|point| {
+                (
+                    point.timestamp(),
+                    score_metaculus_point(question, point, &outcome),
+                )
+            })
+            .collect(),
+    };
+    let points: Vec<(f64, Scores)> = points
+        .into_iter()
+        .filter(|(t, _)| *t <= end)
+        .filter_map(|(t, scores)| Some((t, scores?)))
+        .collect();
+
+    let mut totals = [0.0, 0.0];
+    let mut duration = 0.0;
+    for (i, (t, scores)) in points.iter().enumerate() {
+        let until = points.get(i + 1).map_or(end, |(next, _)| *next);
+        let length = until - t.max(start);
+        if length > 0.0 {
+            for (total, component) in totals.iter_mut().zip(scores.components()) {
+                *total += component * length;
+            }
+            duration += length;
+        }
+    }
+
+    // If every prediction was made at the end of the window, fall back on the last of them.
+    let (_, last) = points.last()?;
+    if duration > 0.0 {
+        Some(last.with_components(totals.map(|total| total / duration)))
+    } else {
+        Some(*last)
+    }
+}
+
+/// The resolution of a question, in the form it is scored against.
+enum Outcome {
+    /// `0.0` or `1.0` for a binary question.
+    Binary(f64),
+    ///
+    /// The resolution on the normalised scale of a continuous question, which is only beyond the
+    /// scale if that bound is open.
+    ///
+    Continuous(f64),
+}
+
+impl Outcome {
+    fn of(question: &Question) -> Option<Outcome> {
+        let resolution = question.resolution?;
+        if matches!(question.get_resolution()?, AmbP | Annulled) {
+            None
+        } else if question.is_binary() {
+            Some(Outcome::Binary(resolution))
+        } else if resolution < 0.0 && !question.has_open_lower_bound() {
+            // As for `Question::get_resolution`, resolutions beyond a closed bound are at the bound.
+            Some(Outcome::Continuous(0.0))
+        } else if resolution > 1.0 && !question.has_open_upper_bound() {
+            Some(Outcome::Continuous(1.0))
+        } else {
+            Some(Outcome::Continuous(resolution))
+        }
+    }
+}
+
+/// A distribution over the normalised scale of a continuous question.
+trait NormalisedDistribution {
+    fn pdf(&self, x: f64) -> f64;
+    fn cdf(&self, x: f64) -> f64;
+}
+
+impl NormalisedDistribution for ContinuousDistribution {
+    fn pdf(&self, x: f64) -> f64 {
+        self.normalised_pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        self.normalised_cdf(x)
+    }
+}
+
+impl NormalisedDistribution for LogisticDistribution {
+    fn pdf(&self, x: f64) -> f64 {
+        self.normalised_pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        self.normalised_cdf(x)
+    }
+}
+
+/// Returns the time the question closed or resolved, whichever came first.
+fn window_end(question: &Question) -> Option<NaiveDateTime> {
+    question
+        .close_time
+        .into_iter()
+        .chain(question.resolve_time)
+        .min()
+}
+
+fn binary_scores(probability: f64, outcome: f64) -> Scores {
+    Scores::Binary {
+        brier: (probability - outcome).powi(2),
+        log: if outcome == 1.0 {
+            probability.ln()
+        } else {
+            (1.0 - probability).ln()
+        },
+    }
+}
+
+fn continuous_scores(distribution: &dyn NormalisedDistribution, resolution: f64) -> Scores {
+    let log = if resolution < 0.0 {
+        distribution.cdf(0.0).ln()
+    } else if resolution > 1.0 {
+        (1.0 - distribution.cdf(1.0)).ln()
+    } else {
+        distribution.pdf(resolution).ln()
+    };
+
+    // Midpoint rule for the integral of (F(x) - H(x - resolution))² over the scale
+    let step = 1.0 / CRPS_STEPS as f64;
+    let crps = (0..CRPS_STEPS)
+        .map(|i| {
+            let x = (i as f64 + 0.5) * step;
+            let observed = if x >= resolution { 1.0 } else { 0.0 };
+            (distribution.cdf(x) - observed).powi(2) * step
+        })
+        .sum();
+
+    Scores::Continuous { log, crps }
+}
+
+fn score_community_point(
+    question: &Question,
+    point: &PredictionTimeseriesPoint,
+    outcome: &Outcome,
+) -> Option<Scores> {
+    match (point, outcome) {
+        (
+            NumericPTP {
+                community_prediction,
+                ..
+            },
+            Outcome::Binary(outcome),
+        ) => Some(binary_scores(*community_prediction, *outcome)),
+        (RangePTP { distribution, .. }, Outcome::Continuous(resolution)) => {
+            match distribution.as_ref()?.with_scale(question.scale())? {
+                TimeseriesDistribution::Logistic(logistic) => {
+                    Some(continuous_scores(&logistic, *resolution))
+                }
+                TimeseriesDistribution::Binary(_) => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn score_metaculus_point(
+    question: &Question,
+    point: &MetaculusPredictionTimeseriesPoint,
+    outcome: &Outcome,
+) -> Option<Scores> {
+    match (point, outcome) {
+        (NumericMPTP { x, .. }, Outcome::Binary(outcome)) => Some(binary_scores(*x, *outcome)),
+        (RangeMPTP { x, .. }, Outcome::Continuous(resolution)) => {
+            // A logistic's quartiles are s·ln(3) either side of its centre.
+            let component = LogisticComponent {
+                x0: x.q2,
+                s: (x.q3? - x.q1?) / (2.0 * 3.0_f64.ln()),
+                w: 1.0,
+            };
+            if component.s.is_nan() || component.s <= 0.0 {
+                return None;
+            }
+            let logistic = LogisticDistribution::new(
+                component,
+                x.low.unwrap_or(0.0),
+                x.high.unwrap_or(1.0),
+                question.scale()?,
+            );
+            Some(continuous_scores(&logistic, *resolution))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,50 @@
+//!
+//! A module for working with the history of the predictions on a question.
+//!
+
+use crate::{Prediction, Quartiles};
+use chrono::{Duration, NaiveDateTime};
+
+///
+/// A single point in the history of an aggregated prediction, as returned by
+/// [Question::community_timeseries](crate::Question::community_timeseries) and
+/// [Question::metaculus_timeseries](crate::Question::metaculus_timeseries).
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct TimeseriesPoint {
+    /// When the prediction was made.
+    pub time: NaiveDateTime,
+    /// The median of the prediction, in real units.
+    pub prediction: Prediction,
+    /// The lower quartile, median and upper quartile of the prediction, if Metaculus recorded them.
+    pub quartiles: Option<Quartiles>,
+    /// The number of predictions that had been made on the question, if Metaculus recorded it.
+    pub num_predictions: Option<u64>,
+}
+
+///
+/// A value sampled at a point in time, as returned by
+/// [Question::resample](crate::Question::resample) and
+/// [Index::resample](crate::index::Index::resample).
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sample<T> {
+    /// When the value was sampled.
+    pub time: NaiveDateTime,
+    /// The value at that time.
+    pub value: T,
+}
+
+///
+/// Returns the times from `start` to `end` (inclusive) at steps of `interval`, or no times at all
+/// if `interval` is not positive.
+///
+pub(crate) fn grid(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    interval: Duration,
+) -> impl Iterator<Item = NaiveDateTime> {
+    let positive = interval > Duration::zero();
+    std::iter::successors(Some(start), move |time| time.checked_add_signed(interval))
+        .take_while(move |time| positive && *time <= end)
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "async")]
+
+mod utils;
+
+use crate::utils::async_fixture_client;
+use futures::StreamExt;
+use metaculustetra::query::QuestionQuery;
+use metaculustetra::Prediction::NumP;
+use metaculustetra::{ApiVersion, AsyncMetaculus, MetaculusError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_async_get_question() {
+    let metaculus = async_fixture_client(Arc::new(AtomicUsize::new(0)), 0);
+
+    assert_eq!(
+        metaculus.get_question("7887").await.unwrap().title_short,
+        "EA Wiki: fraction of EA Forum traffic"
+    );
+    assert_eq!(
+        metaculus.get_prediction_for("7887").await,
+        Some(NumP(0.1132))
+    );
+    assert_eq!(
+        metaculus.try_get_question("1").await.err(),
+        Some(MetaculusError::NotFound {
+            id: "1".to_string()
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_async_api_version() {
+    let metaculus = async_fixture_client(Arc::new(AtomicUsize::new(0)), 0);
+    assert_eq!(metaculus.api_version(), ApiVersion::Auto);
+
+    let metaculus = AsyncMetaculus::builder()
+        .api_version(ApiVersion::Posts)
+        .build_async()
+        .unwrap();
+    assert_eq!(metaculus.api_version(), ApiVersion::Posts);
+}
+
+#[tokio::test]
+async fn test_async_list_questions_error() {
+    let metaculus = async_fixture_client(Arc::new(AtomicUsize::new(0)), 0);
+
+    let questions: Vec<_> = metaculus
+        .list_questions(&QuestionQuery::new().project("1007"))
+        .collect()
+        .await;
+
+    assert_eq!(questions.len(), 1);
+    assert!(matches!(questions[0], Err(MetaculusError::NotFound { .. })));
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn test_async_index_bounded_concurrency() {
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    // The first two requests are only answered once both are in flight
+    let metaculus = async_fixture_client(max_in_flight.clone(), 2);
+
+    let ids = vec!["1493", "7887", "1", "7386", "8541"]
+        .into_iter()
+        .map(|id| id.to_string())
+        .collect();
+    let index = tokio::time::timeout(
+        Duration::from_secs(10),
+        metaculus.create_index_from_questions(ids, vec![1.0, 10.0, 5.0, 1.0, 0.5], 2),
+    )
+    .await
+    .expect("the first two questions were not fetched concurrently");
+
+    assert_eq!(index.questions.len(), 4);
+    assert_eq!(index.questions[1].get_value(), 1.132);
+    // No more than two at once, and both of the first two together
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    // The deprecated constructor ignores ids without a weight
+    let ids = vec!["1493".to_string(), "7887".to_string()];
+    let index = metaculus
+        .create_index_from_questions(ids, vec![1.0], 2)
+        .await;
+    assert_eq!(index.questions.len(), 1);
+    assert_eq!(index.questions[0].question.id, 1493);
+
+    let ids: Vec<String> = vec!["1493", "7887", "1"]
+        .into_iter()
+        .map(|id| id.to_string())
+        .collect();
+    let report = metaculus
+        .try_create_index_from_questions(ids.clone(), vec![1.0, 10.0, 5.0], 2)
+        .await
+        .unwrap();
+    assert_eq!(report.index.questions.len(), 2);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].id, "1");
+    assert!(metaculus
+        .try_create_index_from_questions(ids, vec![1.0], 2)
+        .await
+        .is_err());
+}
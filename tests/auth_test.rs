@@ -0,0 +1,128 @@
+mod utils;
+
+use chrono::NaiveDate;
+use metaculustetra::distribution::{LogisticComponent, LogisticMixture};
+use metaculustetra::forecast::Forecast;
+use metaculustetra::transport::{HttpRequest, HttpResponse};
+use metaculustetra::{Metaculus, MetaculusError};
+
+fn authenticated_client(token: &str) -> Metaculus {
+    Metaculus::builder()
+        .base_url("http://fixtures")
+        .token(token)
+        .transport(
+            |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                let authorised = request
+                    .headers
+                    .contains(&("Authorization".to_string(), "Token good-token".to_string()));
+                if !authorised {
+                    return Ok(HttpResponse {
+                        status: 401,
+                        body: "{\"detail\":\"Invalid token.\"}".to_string(),
+                    });
+                }
+
+                let (fixture, my_predictions) = match request.url.as_str() {
+                    "http://fixtures/api2/questions/8541" => (
+                        "tournament_example",
+                        serde_json::json!({
+                            "predictions": [
+                                {"t": 1637000000.0, "x": 0.25},
+                                {"t": 1637400000.5, "x": 0.35}
+                            ]
+                        }),
+                    ),
+                    "http://fixtures/api2/questions/7887" => (
+                        "range_example",
+                        serde_json::json!({
+                            "predictions": [{
+                                "t": 1637000000.0,
+                                "d": {
+                                    "kind": "multi",
+                                    "d": [
+                                        {"kind": "logistic", "x0": 0.1, "s": 0.05, "w": 0.75, "low": 0.0, "high": 1.0},
+                                        {"kind": "logistic", "x0": 0.3, "s": 0.1, "w": 0.25, "low": 0.0, "high": 1.0}
+                                    ]
+                                }
+                            }]
+                        }),
+                    ),
+                    _ => panic!("unexpected request to {}", request.url),
+                };
+                let mut question: serde_json::Value = serde_json::from_str(
+                    &std::fs::read_to_string(format!("tests/{}.json", fixture)).unwrap(),
+                )
+                .unwrap();
+                question["my_predictions"] = my_predictions;
+
+                Ok(HttpResponse {
+                    status: 200,
+                    body: question.to_string(),
+                })
+            },
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_rejected_token() {
+    let metaculus = authenticated_client("bad-token");
+
+    assert_eq!(
+        metaculus.try_get_question("8541").err(),
+        Some(MetaculusError::Unauthorized {
+            status: 401,
+            body: "{\"detail\":\"Invalid token.\"}".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_my_binary_predictions() {
+    let question = authenticated_client("good-token")
+        .try_get_question("8541")
+        .unwrap();
+    let predictions = question.get_my_predictions();
+
+    assert_eq!(predictions.len(), 2);
+    assert_eq!(
+        predictions[0].time,
+        NaiveDate::from_ymd_opt(2021, 11, 15)
+            .unwrap()
+            .and_hms_opt(18, 13, 20)
+            .unwrap()
+    );
+    assert_eq!(predictions[0].forecast, Forecast::Probability(0.25));
+    assert_eq!(predictions[1].forecast, Forecast::Probability(0.35));
+}
+
+#[test]
+fn test_my_continuous_predictions() {
+    let question = authenticated_client("good-token")
+        .try_get_question("7887")
+        .unwrap();
+
+    assert_eq!(
+        question.get_my_predictions()[0].forecast,
+        Forecast::Continuous(LogisticMixture::new(vec![
+            LogisticComponent {
+                x0: 0.1,
+                s: 0.05,
+                w: 0.75
+            },
+            LogisticComponent {
+                x0: 0.3,
+                s: 0.1,
+                w: 0.25
+            },
+        ]))
+    );
+}
+
+#[test]
+fn test_no_predictions_without_token() {
+    let question = utils::read_q_from_file("tournament_example");
+
+    assert!(question.get_my_predictions().is_empty());
+}
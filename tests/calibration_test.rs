@@ -0,0 +1,58 @@
+mod utils;
+
+use crate::utils::read_q_from_file;
+use chrono::Duration;
+use metaculustetra::calibration::calibrate;
+use metaculustetra::scoring::Aggregate;
+use metaculustetra::Question;
+
+/// Reads a fixture with its resolution replaced by `resolution`.
+fn resolved_as(name: &str, resolution: f64) -> Question {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(format!("tests/{}.json", name)).unwrap())
+            .unwrap();
+    json["resolution"] = resolution.into();
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn test_calibration_buckets() {
+    // Predicted 99%, 99% and 20% at close, resolving yes, no and no
+    let questions = vec![
+        read_q_from_file("resolved_probability_example"),
+        resolved_as("resolved_probability_example", 0.0),
+        resolved_as("probability_example", 0.0),
+    ];
+    let report = calibrate(&questions, Aggregate::Community, Duration::zero(), 10);
+
+    assert_eq!(report.count, 3);
+    assert_eq!(report.buckets.len(), 2);
+
+    let low = &report.buckets[0];
+    assert_eq!((low.lower, low.upper), (0.2, 0.3));
+    assert_eq!(low.count, 1);
+    assert_eq!(low.observed_frequency, 0.0);
+
+    let high = &report.buckets[1];
+    assert_eq!(high.count, 2);
+    assert_eq!(high.mean_prediction, 0.99);
+    assert_eq!(high.observed_frequency, 0.5);
+    assert!(high.confidence_interval.0 < 0.5 && high.confidence_interval.1 > 0.5);
+
+    // (1 × |0.2 - 0| + 2 × |0.99 - 0.5|) / 3
+    assert!((report.calibration_error - 0.3933333).abs() < 1e-6);
+}
+
+#[test]
+fn test_calibration_excludes_unusable_questions() {
+    let questions = vec![
+        read_q_from_file("probability_example"),
+        read_q_from_file("ambiguously_resolved_example"),
+        read_q_from_file("resolved_range_example"),
+    ];
+    let report = calibrate(&questions, Aggregate::Metaculus, Duration::days(1), 5);
+
+    assert_eq!(report.count, 0);
+    assert!(report.buckets.is_empty());
+    assert_eq!(report.calibration_error, 0.0);
+}
@@ -0,0 +1,137 @@
+mod utils;
+
+use crate::utils::fixture_client;
+use metaculustetra::transport::{HttpRequest, HttpResponse};
+use metaculustetra::Prediction::NumP;
+use metaculustetra::{Metaculus, MetaculusError};
+
+#[test]
+fn test_invalid_domain_is_transport_error() {
+    let metaculus = Metaculus::with_domain("not a domain");
+
+    assert!(matches!(
+        metaculus.try_get_question("7887"),
+        Err(MetaculusError::Transport(_))
+    ));
+    assert!(metaculus.get_question("7887").is_none());
+    assert!(metaculus.try_get_prediction_for("7887").is_err());
+    assert_eq!(metaculus.get_prediction_for("7887"), None);
+}
+
+#[test]
+fn test_domain_constructors() {
+    assert_eq!(
+        Metaculus::new("pandemic").base_url(),
+        "https://pandemic.metaculus.com"
+    );
+    assert_eq!(
+        Metaculus::default().base_url(),
+        Metaculus::standard().base_url()
+    );
+}
+
+#[test]
+fn test_fixture_transport() {
+    let metaculus = fixture_client();
+
+    assert_eq!(
+        metaculus.get_question("7887").unwrap().title_short,
+        "EA Wiki: fraction of EA Forum traffic"
+    );
+    assert_eq!(metaculus.get_prediction_for("7887"), Some(NumP(0.1132)));
+    assert_eq!(metaculus.get_numeric_prediction_for("7887"), Some(0.1132));
+    assert_eq!(
+        metaculus.try_get_question("1").err(),
+        Some(MetaculusError::NotFound {
+            id: "1".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_status_and_deserialize_errors() {
+    let rate_limited = Metaculus::builder()
+        .transport(|_: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+            Ok(HttpResponse {
+                status: 429,
+                body: "Too many requests".to_string(),
+            })
+        })
+        .build()
+        .unwrap();
+    assert_eq!(
+        rate_limited.try_get_question("7887").err(),
+        Some(MetaculusError::Status {
+            status: 429,
+            body: "Too many requests".to_string()
+        })
+    );
+
+    let schema_changed = Metaculus::builder()
+        .transport(|_: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+            Ok(HttpResponse {
+                status: 200,
+                body: r#"{"title_short": "Test", "possibilities": {}}"#.to_string(),
+            })
+        })
+        .build()
+        .unwrap();
+    match schema_changed.try_get_question("7887") {
+        Err(MetaculusError::Deserialize { path, .. }) => assert_eq!(path, "possibilities"),
+        _ => panic!("expected a deserialization error"),
+    }
+}
+
+#[test]
+fn test_builder_sends_user_agent() {
+    let metaculus = Metaculus::builder()
+        .domain("pandemic")
+        .user_agent("metaculustetra-tests")
+        .transport(
+            |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                assert_eq!(
+                    request.url,
+                    "https://pandemic.metaculus.com/api2/questions/7887"
+                );
+                assert!(request
+                    .headers
+                    .contains(&("User-Agent".to_string(), "metaculustetra-tests".to_string())));
+                Err(MetaculusError::Transport("offline".to_string()))
+            },
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(metaculus.base_url(), "https://pandemic.metaculus.com");
+    assert_eq!(
+        metaculus.try_get_question("7887").err(),
+        Some(MetaculusError::Transport("offline".to_string()))
+    );
+}
+
+#[test]
+fn test_error_display() {
+    assert_eq!(
+        MetaculusError::Status {
+            status: 429,
+            body: "Too many requests".to_string()
+        }
+        .to_string(),
+        "HTTP status 429: Too many requests"
+    );
+    assert_eq!(
+        MetaculusError::Deserialize {
+            path: "possibilities.scale".to_string(),
+            message: "missing field `min`".to_string()
+        }
+        .to_string(),
+        "failed to deserialize `possibilities.scale`: missing field `min`"
+    );
+    assert_eq!(
+        MetaculusError::NotFound {
+            id: "7887".to_string()
+        }
+        .to_string(),
+        "7887 not found"
+    );
+}
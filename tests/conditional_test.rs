@@ -0,0 +1,120 @@
+mod utils;
+
+use crate::utils::fixture_client;
+use metaculustetra::conditional::{Branch, ConditionalQuestion};
+use metaculustetra::transport::{HttpRequest, HttpResponse};
+use metaculustetra::Prediction::AmbP;
+use metaculustetra::{ApiVersion, Metaculus, MetaculusError};
+
+fn fixture(name: &str) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(format!("tests/{}.json", name)).unwrap()).unwrap()
+}
+
+/// Makes the JSON for a conditional pair out of the fixtures with the given names.
+fn conditional_json(condition: &str, outcome: &str, if_yes: &str, if_no: &str) -> String {
+    serde_json::json!({
+        "id": 99,
+        "condition": fixture(condition),
+        "condition_child": fixture(outcome),
+        "question_yes": fixture(if_yes),
+        "question_no": fixture(if_no),
+    })
+    .to_string()
+}
+
+fn read_conditional(
+    condition: &str,
+    outcome: &str,
+    if_yes: &str,
+    if_no: &str,
+) -> ConditionalQuestion {
+    serde_json::from_str(&conditional_json(condition, outcome, if_yes, if_no)).unwrap()
+}
+
+#[test]
+fn test_get_conditional() {
+    let body = conditional_json(
+        "probability_example",
+        "tournament_example",
+        "probability_example",
+        "tournament_example",
+    );
+    let metaculus = Metaculus::builder()
+        .base_url("http://fixtures")
+        .transport(
+            move |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                assert_eq!(request.url, "http://fixtures/api2/questions/99");
+                Ok(HttpResponse {
+                    status: 200,
+                    body: body.clone(),
+                })
+            },
+        )
+        .build()
+        .unwrap();
+
+    let conditional = metaculus.get_conditional("99").unwrap();
+    assert_eq!(conditional.id, 99);
+    assert_eq!(
+        conditional.outcome.title_short,
+        "US rejoin Iran Nuclear Deal by 2024?"
+    );
+    assert_eq!(conditional.get_probability_if_yes(), Some(0.2));
+    assert_eq!(conditional.get_probability_if_no(), Some(0.3));
+    assert_eq!(conditional.get_condition_resolution(), None);
+    assert_eq!(conditional.get_resolution(), None);
+    assert_eq!(
+        conditional
+            .branch(Branch::IfYes)
+            .community_timeseries()
+            .count(),
+        101
+    );
+}
+
+#[test]
+fn test_conditional_fixture() {
+    // An `/api2/questions/{id}` response for a conditional pair, cut down to the fields read from
+    // the pair and its four linked questions
+    let metaculus = fixture_client();
+
+    let conditional = metaculus.try_get_conditional("90003").unwrap();
+    assert_eq!(conditional.id, 90003);
+    assert_eq!(conditional.condition.id, 8541);
+    assert_eq!(conditional.outcome.id, 1493);
+    assert_eq!(conditional.if_yes.id, 90004);
+    assert_eq!(conditional.if_no.id, 90005);
+    assert_eq!(conditional.get_probability_if_yes(), Some(0.2));
+    assert_eq!(conditional.get_probability_if_no(), Some(0.15));
+    assert_eq!(conditional.get_condition_resolution(), None);
+    assert_eq!(conditional.get_resolution(), None);
+
+    let body = std::fs::read_to_string("tests/conditional_example.json").unwrap();
+    let parsed = ConditionalQuestion::from_json(&body, ApiVersion::Auto).unwrap();
+    assert_eq!(parsed.if_no.title_short, conditional.if_no.title_short);
+}
+
+#[test]
+fn test_resolved_condition() {
+    // The condition resolved yes, so the "if no" branch was annulled
+    let conditional = read_conditional(
+        "resolved_probability_example",
+        "tournament_example",
+        "probability_example",
+        "ambiguously_resolved_example",
+    );
+    assert_eq!(conditional.get_condition_resolution(), Some(Branch::IfYes));
+    assert_eq!(conditional.get_probability_if_yes(), Some(0.2));
+    assert_eq!(conditional.get_probability_if_no(), None);
+    // Neither the branch nor the outcome has resolved yet
+    assert_eq!(conditional.get_resolution(), None);
+
+    let conditional = read_conditional(
+        "ambiguously_resolved_example",
+        "tournament_example",
+        "probability_example",
+        "tournament_example",
+    );
+    assert_eq!(conditional.get_condition_resolution(), None);
+    assert_eq!(conditional.get_resolution(), Some(AmbP));
+}
@@ -0,0 +1,226 @@
+mod utils;
+
+use crate::utils::{assert_close, read_edited_q_from_file, read_q_from_file};
+use chrono::NaiveDate;
+use metaculustetra::distribution::TimeseriesDistribution;
+use metaculustetra::Prediction::{DatP, NumP};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_community_distribution_quartiles() {
+    let question = read_q_from_file("resolved_range_example");
+    let distribution = question.get_community_distribution().unwrap();
+
+    // Matches the q1, q2 and q3 reported by Metaculus
+    assert_close(
+        distribution
+            .quantile(0.25)
+            .unwrap()
+            .get_if_numeric()
+            .unwrap(),
+        0.46304 * 50.0 - 20.0,
+        0.01,
+    );
+    assert_close(
+        distribution.median().unwrap().get_if_numeric().unwrap(),
+        0.49337 * 50.0 - 20.0,
+        0.01,
+    );
+    assert_close(
+        distribution
+            .quantile(0.75)
+            .unwrap()
+            .get_if_numeric()
+            .unwrap(),
+        0.52384 * 50.0 - 20.0,
+        0.01,
+    );
+    assert_close(distribution.mass_below_lower_bound(), 0.01073, 1e-12);
+    assert_close(distribution.mass_above_upper_bound(), 1.0 - 0.98912, 1e-12);
+    assert_eq!(distribution.quantile(0.005), None);
+    assert_eq!(distribution.quantile(0.995), None);
+}
+
+#[test]
+fn test_community_distribution_cdf_and_pdf() {
+    let question = read_q_from_file("resolved_range_example");
+    let distribution = question.get_community_distribution().unwrap();
+
+    assert_close(distribution.cdf(&NumP(-20.0)).unwrap(), 0.01073, 1e-12);
+    assert_close(distribution.cdf(&NumP(30.0)).unwrap(), 0.98912, 1e-12);
+    assert_close(distribution.cdf(&NumP(4.6685)).unwrap(), 0.5, 0.001);
+    assert_eq!(distribution.cdf(&NumP(31.0)), None);
+    assert_eq!(distribution.pdf(&NumP(-21.0)), None);
+
+    // The density per percentage point is the normalised density divided by the width of the scale
+    let mean_density =
+        (distribution.cdf(&NumP(5.0)).unwrap() - distribution.cdf(&NumP(4.0)).unwrap()) / 1.0;
+    assert_close(distribution.pdf(&NumP(4.5)).unwrap(), mean_density, 0.005);
+    assert_close(
+        distribution.mean().unwrap().get_if_numeric().unwrap(),
+        4.70,
+        0.01,
+    );
+}
+
+#[test]
+fn test_logarithmic_distribution() {
+    let question = read_q_from_file("logarithmic_range_example");
+    let distribution = question.get_community_distribution().unwrap();
+    let median = distribution.median().unwrap().get_if_numeric().unwrap();
+
+    assert_close(
+        median.log10(),
+        question
+            .get_community_prediction()
+            .unwrap()
+            .get_if_numeric()
+            .unwrap()
+            .log10(),
+        0.001,
+    );
+    assert_close(distribution.cdf(&NumP(median)).unwrap(), 0.5, 1e-9);
+}
+
+#[test]
+fn test_date_distribution() {
+    let question = read_q_from_file("date_range_example");
+    let distribution = question.get_community_distribution().unwrap();
+
+    let median = distribution.median().unwrap().get_if_date().unwrap();
+    assert_eq!(median.date(), NaiveDate::from_ymd_opt(2022, 2, 22).unwrap());
+    assert_eq!(distribution.mass_below_lower_bound(), 0.0);
+    assert_close(distribution.mass_above_upper_bound(), 0.0119, 1e-12);
+    assert_eq!(distribution.cdf(&NumP(0.0)), None);
+    assert_close(distribution.cdf(&DatP(median)).unwrap(), 0.5, 1e-6);
+}
+
+#[test]
+fn test_no_distribution() {
+    assert!(read_q_from_file("probability_example")
+        .get_community_distribution()
+        .is_none());
+    assert!(read_q_from_file("discussion_example")
+        .get_community_distribution()
+        .is_none());
+    assert!(read_q_from_file("range_example")
+        .get_metaculus_distribution()
+        .is_none());
+    assert!(read_q_from_file("resolved_range_example")
+        .get_metaculus_distribution()
+        .is_some());
+}
+
+#[test]
+fn test_timeseries_logistic_distribution() {
+    let question = read_q_from_file("resolved_range_example");
+    let distribution = question.get_timeseries_distribution().unwrap();
+    let logistic = match &distribution {
+        TimeseriesDistribution::Logistic(logistic) => logistic,
+        TimeseriesDistribution::Binary(_) => panic!("expected a logistic distribution"),
+    };
+    assert_close(logistic.component().x0, 0.50627, 1e-12);
+
+    // Matches the probabilities beyond the bounds reported by Metaculus
+    assert_close(distribution.cdf(&NumP(-20.0)).unwrap(), 0.01025, 1e-9);
+    assert_close(distribution.cdf(&NumP(30.0)).unwrap(), 0.98965, 1e-9);
+    assert_eq!(distribution.cdf(&DatP(NaiveDate::default().into())), None);
+
+    for p in [0.005, 0.1, 0.5, 0.9, 0.995] {
+        let x = distribution.quantile(p).unwrap();
+        assert_close(distribution.cdf(&x).unwrap(), p, 1e-9);
+    }
+    assert_eq!(distribution.quantile(1.0), None);
+
+    let earliest = question
+        .get_timeseries_distribution_before(
+            NaiveDate::from_ymd_opt(2021, 6, 16)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+        .unwrap();
+    let median = earliest.quantile(0.5).unwrap().get_if_numeric().unwrap();
+    assert_close(median, 0.5503 * 50.0 - 20.0, 0.1);
+}
+
+#[test]
+fn test_timeseries_binary_distribution() {
+    let question = read_q_from_file("probability_example");
+    let distribution = question.get_timeseries_distribution().unwrap();
+
+    let median = distribution.quantile(0.5).unwrap();
+    assert_close(distribution.cdf(&median).unwrap(), 0.5, 1e-9);
+    assert_eq!(distribution.cdf(&NumP(1.0)), Some(1.0));
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let samples: Vec<f64> = (0..4000)
+        .map(|_| {
+            distribution
+                .sample(&mut rng)
+                .unwrap()
+                .get_if_numeric()
+                .unwrap()
+        })
+        .collect();
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+    assert_close(mean, 0.30703, 0.02);
+    assert_close(variance, 0.05699, 0.01);
+
+    // Every forecast was the same when the question opened
+    let first = question
+        .get_timeseries_distribution_before(
+            NaiveDate::from_ymd_opt(2018, 10, 12)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(first.quantile(0.9), Some(NumP(0.2)));
+}
+
+#[test]
+fn test_unknown_timeseries_distribution() {
+    // Distributions in shapes the crate does not know are ignored rather than failing the question
+    let question = read_q_from_file("unknown_distribution_example");
+    let times: Vec<_> = question.community_timeseries().map(|p| p.time).collect();
+    assert_eq!(times.len(), 4);
+
+    let shapes: Vec<bool> = times
+        .iter()
+        .map(|time| question.get_timeseries_distribution_before(*time).is_some())
+        .collect();
+    assert_eq!(shapes, vec![true, false, false, false]);
+}
+
+#[test]
+fn test_narrow_timeseries_distribution() {
+    // So narrow that its cdf is exactly 0 at the minimum and 1 at the maximum of the scale
+    let question = read_edited_q_from_file("resolved_range_example", |json| {
+        for point in json["prediction_timeseries"].as_array_mut().unwrap() {
+            point["distribution"] = serde_json::json!({
+                "kind": "logistic", "x0": 0.5, "s": 0.0005, "low": 0.01, "high": 0.99
+            });
+        }
+    });
+    let distribution = question.get_timeseries_distribution().unwrap();
+
+    assert_eq!(distribution.cdf(&NumP(-30.0)), Some(0.01));
+    assert_eq!(distribution.cdf(&NumP(40.0)), Some(0.99));
+    assert_close(distribution.cdf(&NumP(5.0)).unwrap(), 0.5, 1e-9);
+
+    // The probability beyond each bound is placed at the bound
+    assert_eq!(distribution.quantile(0.005), Some(NumP(-20.0)));
+    assert_eq!(distribution.quantile(0.995), Some(NumP(30.0)));
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let x = distribution
+            .sample(&mut rng)
+            .unwrap()
+            .get_if_numeric()
+            .unwrap();
+        assert!((-20.0..=30.0).contains(&x));
+    }
+}
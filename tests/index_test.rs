@@ -1,6 +1,15 @@
 mod utils;
-use crate::utils::read_q_from_file;
-use metaculustetra::index::{Index, WeightedQuestion};
+use crate::utils::{fixture_client, read_edited_q_from_file, read_q_from_file};
+use chrono::{Duration, NaiveDate};
+use metaculustetra::index::{
+    Index, IndexDefinition, MetaculusIndexCreator, Normalisation, QuestionDefinition, SkipReason,
+    SkippedQuestion, WeightedQuestion,
+};
+use metaculustetra::transport::{HttpRequest, HttpResponse};
+use metaculustetra::Prediction::NumP;
+use metaculustetra::{ApiVersion, Metaculus, MetaculusError, Question};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 #[test]
 fn test_weighted_question() {
@@ -48,3 +57,654 @@ fn test_continuous_index() {
     assert_eq!(index.questions[2].get_value(), 2.2);
     assert_eq!(index.get_index_value(), 2.0 + 1.132 + 2.2)
 }
+
+#[test]
+fn test_out_of_bounds_resolution_index() {
+    let above = read_edited_q_from_file("resolved_range_example", |json| {
+        json["resolution"] = 1.2.into();
+    });
+    let below = read_edited_q_from_file("resolved_range_example", |json| {
+        json["resolution"] = (-0.3).into();
+    });
+
+    // Resolutions beyond the bounds count as resolving at the bounds, from -20 to 30
+    let above = WeightedQuestion::create_from_range(&above, 0.1).unwrap();
+    let below = WeightedQuestion::create_from_range(&below, 0.1).unwrap();
+    assert_eq!(above.get_value(), 5.0);
+    assert_eq!(below.get_value(), 0.0);
+}
+
+#[test]
+fn test_annulled_questions_are_ignored() {
+    let annulled = read_edited_q_from_file("resolved_probability_example", |json| {
+        json["resolution"] = (-2.0).into();
+    });
+    let ambiguous = read_q_from_file("ambiguously_resolved_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&annulled, 1.0).unwrap(),
+            WeightedQuestion::create_from_binary(&ambiguous, 1.0).unwrap(),
+        ],
+    };
+    assert_eq!(index.get_index_value(), 0.0);
+}
+
+#[test]
+fn test_resample_index() {
+    let question_1 = read_q_from_file("probability_example");
+    let question_2 = read_q_from_file("resolved_probability_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&question_1, 1.0).unwrap(),
+            WeightedQuestion::create_from_binary(&question_2, 2.0).unwrap(),
+        ],
+    };
+
+    let start = NaiveDate::from_ymd_opt(2021, 10, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let weekly = index.resample(start, start + Duration::weeks(8), Duration::weeks(1));
+
+    // The second question was published on 14 October 2021 and resolved yes on 8 November 2021,
+    // and the Metaculus prediction on it is used until then
+    let expected = [
+        0.24,
+        0.24,
+        0.2 + 2.0 * 0.6011265726456335,
+        0.2 + 2.0 * 0.6916388975976903,
+        0.2 + 2.0 * 0.7288358799293652,
+        0.2 + 2.0 * 0.6954055950736547,
+        2.2,
+        2.2,
+        2.2,
+    ];
+    assert_eq!(weekly.len(), expected.len());
+    for (i, (sample, expected)) in weekly.iter().zip(expected).enumerate() {
+        assert_eq!(sample.time, start + Duration::weeks(i as i64));
+        assert!(
+            (sample.value - expected).abs() < 1e-9,
+            "{}: expected {}, got {}",
+            sample.time,
+            expected,
+            sample.value
+        );
+    }
+}
+
+#[test]
+fn test_index_breakdown() {
+    let question_1 = read_q_from_file("probability_example");
+    let question_2 = read_q_from_file("resolved_probability_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&question_1, 1.0).unwrap(),
+            WeightedQuestion::create_from_binary(&question_2, 2.0).unwrap(),
+        ],
+    };
+
+    let breakdown = index.breakdown();
+    assert_eq!(breakdown.len(), 2);
+    assert_eq!(breakdown[0].id, 1493);
+    assert_eq!(breakdown[0].title, question_1.title);
+    assert_eq!(breakdown[0].prediction, Some(NumP(0.2)));
+    assert_eq!(breakdown[0].value, Some(0.2));
+    assert_eq!(breakdown[1].weight, 2.0);
+    assert_eq!(breakdown[1].contribution, 2.0);
+    assert_eq!(
+        breakdown.iter().map(|c| c.contribution).sum::<f64>(),
+        index.get_index_value()
+    );
+
+    // Nothing had been predicted before the second question was published
+    let before_publication = NaiveDate::from_ymd_opt(2021, 10, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let early = &index.breakdown_before(before_publication)[1];
+    assert_eq!((early.prediction.as_ref(), early.value), (None, None));
+    assert_eq!(early.contribution, 0.0);
+}
+
+#[test]
+fn test_index_diff() {
+    let question_1 = read_q_from_file("probability_example");
+    let question_2 = read_q_from_file("resolved_probability_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&question_1, 1.0).unwrap(),
+            WeightedQuestion::create_from_binary(&question_2, 2.0).unwrap(),
+        ],
+    };
+
+    let from = NaiveDate::from_ymd_opt(2021, 10, 20)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let to = NaiveDate::from_ymd_opt(2021, 11, 10)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let diff = index.diff(from, to);
+
+    assert_eq!(diff.from_value, index.get_index_value_before(from));
+    assert_eq!(diff.to_value, index.get_index_value_before(to));
+    assert_eq!(diff.change, diff.to_value - diff.from_value);
+    assert_eq!(diff.components.len(), 2);
+    let attributed = diff.components.iter().map(|c| c.change).sum::<f64>();
+    assert!((attributed - diff.change).abs() < 1e-12);
+
+    // The second question resolved yes in between
+    let resolved = &diff.components[1];
+    assert_eq!(resolved.after.prediction, Some(NumP(1.0)));
+    assert_eq!(resolved.change, 2.0 - resolved.before.contribution);
+}
+
+#[test]
+fn test_normalised_index() {
+    let question_1 = read_q_from_file("probability_example");
+    let question_2 = read_q_from_file("resolved_range_example");
+    let question_3 = read_q_from_file("date_range_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&question_1, 1.0).unwrap(),
+            WeightedQuestion::create_from_range(&question_2, 1.0).unwrap(),
+            WeightedQuestion::create_from_date(&question_3, 1.0).unwrap(),
+        ],
+    };
+
+    assert_eq!(
+        index.get_normalised_value(Normalisation::Raw),
+        index.get_index_value()
+    );
+
+    // Resolved at 0.44 of the way from -20 to 30
+    let far_future = NaiveDate::from_ymd_opt(2100, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let date = &index.questions[2];
+    let position = date
+        .get_normalised_value_before(far_future, Normalisation::ScaleRange)
+        .unwrap();
+    assert!((0.0..=1.0).contains(&position));
+    let expected = 0.2 + 0.44 + position;
+    assert!((index.get_normalised_value(Normalisation::ScaleRange) - expected).abs() < 1e-9);
+
+    let percentage = index.get_percentage().unwrap();
+    assert!((percentage - expected / 3.0 * 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_percentage_index() {
+    let question_1 = read_q_from_file("probability_example");
+    let question_2 = read_q_from_file("resolved_range_example");
+    let ambiguous = read_q_from_file("ambiguously_resolved_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&question_1, -1.0).unwrap(),
+            WeightedQuestion::create_from_range(&question_2, 3.0).unwrap(),
+            WeightedQuestion::create_from_binary(&ambiguous, 10.0).unwrap(),
+        ],
+    };
+
+    // The negatively weighted question counts as 1 - 0.2, and the ambiguous one is left out
+    let percentage = index.get_percentage().unwrap();
+    assert!((percentage - (0.8 + 3.0 * 0.44) / 4.0 * 100.0).abs() < 1e-9);
+
+    let empty = Index {
+        questions: vec![WeightedQuestion::create_from_binary(&ambiguous, 1.0).unwrap()],
+    };
+    assert_eq!(empty.get_percentage(), None);
+}
+
+#[test]
+fn test_z_score_index() {
+    let question = read_q_from_file("probability_example");
+    let weighted = WeightedQuestion::create_from_binary(&question, 2.0).unwrap();
+
+    let history: Vec<f64> = question
+        .community_timeseries()
+        .map(|point| point.prediction.get_if_numeric().unwrap())
+        .collect();
+    let mean = history.iter().sum::<f64>() / history.len() as f64;
+    let sd =
+        (history.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / history.len() as f64).sqrt();
+    let current = weighted.get_value() / 2.0;
+
+    let index = Index {
+        questions: vec![weighted],
+    };
+    let z = index.get_normalised_value(Normalisation::ZScore);
+    assert!((z - 2.0 * (current - mean) / sd).abs() < 1e-9);
+
+    // Too little history to measure against
+    let start = question.community_timeseries().next().unwrap().time;
+    assert_eq!(
+        index.get_normalised_value_before(start, Normalisation::ZScore),
+        0.0
+    );
+}
+
+#[test]
+fn test_z_score_uses_the_same_aggregate() {
+    // Before it resolved, this question is valued at its Metaculus prediction, so it is measured
+    // against the history of the Metaculus prediction rather than the community's
+    let question = read_q_from_file("resolved_probability_example");
+    let index = Index {
+        questions: vec![WeightedQuestion::create_from_binary(&question, 1.0).unwrap()],
+    };
+    let date = NaiveDate::from_ymd_opt(2021, 11, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let z = index.get_normalised_value_before(date, Normalisation::ZScore);
+    assert!((z - 1.0789152730143623).abs() < 1e-9, "{}", z);
+
+    // Predictions after the question closed are not part of its history
+    let closed = |json: &mut serde_json::Value| {
+        json["close_time"] = "2021-10-20T00:00:00Z".into();
+    };
+    let question = read_edited_q_from_file("probability_example", closed);
+    let late = read_edited_q_from_file("probability_example", |json| {
+        closed(json);
+        json["prediction_timeseries"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({
+                "t": 1635000000.0,
+                "community_prediction": 0.9,
+                "num_predictions": 320,
+            }));
+    });
+    let z_score = |question: &Question| {
+        Index {
+            questions: vec![WeightedQuestion::create_from_binary(question, 1.0).unwrap()],
+        }
+        .get_normalised_value(Normalisation::ZScore)
+    };
+    assert_eq!(z_score(&late), z_score(&question));
+}
+
+#[test]
+fn test_index_uncertainty() {
+    let binary = read_q_from_file("probability_example");
+    let range = read_q_from_file("range_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&binary, 1.0).unwrap(),
+            WeightedQuestion::create_from_range(&range, 10.0).unwrap(),
+        ],
+    };
+
+    let uncertainty = index.get_uncertainty(2000, &mut StdRng::seed_from_u64(7));
+    assert_eq!(
+        uncertainty,
+        index.get_uncertainty(2000, &mut StdRng::seed_from_u64(7))
+    );
+    assert!(uncertainty.interval_90.0 <= uncertainty.interval_50.0);
+    assert!(uncertainty.interval_50.0 <= uncertainty.median);
+    assert!(uncertainty.median <= uncertainty.interval_50.1);
+    assert!(uncertainty.interval_50.1 <= uncertainty.interval_90.1);
+
+    // The binary question resolves yes with probability 0.2, so most draws come from the range
+    // question alone, whose median is that of its community distribution
+    let median = range
+        .get_timeseries_distribution()
+        .unwrap()
+        .quantile(0.5)
+        .unwrap()
+        .get_if_numeric()
+        .unwrap();
+    let range_only = Index {
+        questions: vec![WeightedQuestion::create_from_range(&range, 10.0).unwrap()],
+    }
+    .get_uncertainty(2000, &mut StdRng::seed_from_u64(7));
+    assert!((range_only.median - 10.0 * median).abs() < 0.2);
+}
+
+#[test]
+fn test_binary_index_uncertainty() {
+    let binary = read_q_from_file("probability_example");
+    let resolved = read_q_from_file("resolved_probability_example");
+    let ambiguous = read_q_from_file("ambiguously_resolved_example");
+
+    let index = Index {
+        questions: vec![
+            WeightedQuestion::create_from_binary(&binary, 1.0).unwrap(),
+            WeightedQuestion::create_from_binary(&resolved, 2.0).unwrap(),
+            WeightedQuestion::create_from_binary(&ambiguous, 5.0).unwrap(),
+        ],
+    };
+
+    // Each draw resolves the first question yes or no, with probability 0.2 of yes
+    let uncertainty = index.get_uncertainty(1000, &mut StdRng::seed_from_u64(0));
+    assert_eq!(uncertainty.median, 2.0);
+    assert_eq!(uncertainty.interval_50, (2.0, 2.0));
+    assert_eq!(uncertainty.interval_90, (2.0, 3.0));
+
+    // Before the second question resolved, it is drawn too
+    let before_resolution = NaiveDate::from_ymd_opt(2021, 11, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let uncertainty =
+        index.get_uncertainty_before(before_resolution, 1000, &mut StdRng::seed_from_u64(0));
+    assert!(uncertainty.interval_90.0 <= 1.0);
+    assert!(uncertainty.interval_90.1 >= 2.0);
+}
+
+#[test]
+fn test_continuous_index_uncertainty_without_mixture() {
+    // Without the logistic or the full distribution, the draws follow the community quartiles
+    let quartiles_only = read_edited_q_from_file("range_example", |json| {
+        json["community_prediction"]["full"] = serde_json::Value::Null;
+        for point in json["prediction_timeseries"].as_array_mut().unwrap() {
+            point["distribution"] = serde_json::Value::Null;
+        }
+    });
+    let index = Index {
+        questions: vec![WeightedQuestion::create_from_range(&quartiles_only, 1.0).unwrap()],
+    };
+    let uncertainty = index.get_uncertainty(4000, &mut StdRng::seed_from_u64(3));
+    assert!(uncertainty.without_distribution.is_empty());
+    assert!((uncertainty.median - 0.1132).abs() < 0.01);
+    assert!((uncertainty.interval_50.0 - 0.06033).abs() < 0.01);
+    assert!((uncertainty.interval_50.1 - 0.18405).abs() < 0.01);
+
+    // Questions from the posts API are drawn from their full community distribution
+    let body = std::fs::read_to_string("tests/posts_range_example.json").unwrap();
+    let unresolved = Question::from_json(
+        &body.replace("\"resolution\": \"100.0\"", "\"resolution\": null"),
+        ApiVersion::Posts,
+    )
+    .unwrap();
+    assert!(unresolved.get_resolution().is_none());
+    let index = Index {
+        questions: vec![WeightedQuestion::create_from_range(&unresolved, 1.0).unwrap()],
+    };
+    let uncertainty = index.get_uncertainty(4000, &mut StdRng::seed_from_u64(3));
+    assert!(uncertainty.without_distribution.is_empty());
+    assert!(uncertainty.interval_90.0 < uncertainty.interval_90.1);
+    let median = unresolved.get_community_distribution().unwrap().median();
+    let median = median.unwrap().get_if_numeric().unwrap();
+    let zero = index.questions[0].zero;
+    assert!((uncertainty.median - (median / zero).ln()).abs() < 0.05);
+
+    // With neither, the question is held at its point forecast and reported
+    let point_only = read_edited_q_from_file("range_example", |json| {
+        json["community_prediction"]["full"] = serde_json::Value::Null;
+        for point in json["prediction_timeseries"].as_array_mut().unwrap() {
+            point["distribution"] = serde_json::Value::Null;
+            point["community_prediction"]["q1"] = serde_json::Value::Null;
+            point["community_prediction"]["q3"] = serde_json::Value::Null;
+        }
+    });
+    let index = Index {
+        questions: vec![WeightedQuestion::create_from_range(&point_only, 1.0).unwrap()],
+    };
+    let uncertainty = index.get_uncertainty(100, &mut StdRng::seed_from_u64(3));
+    assert_eq!(uncertainty.without_distribution, vec![point_only.id]);
+    assert_eq!(uncertainty.interval_90.0, uncertainty.interval_90.1);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_create_index_from_questions() {
+    let metaculus = fixture_client();
+    let ids: Vec<String> = vec!["1493", "956", "7887"]
+        .into_iter()
+        .map(|id| id.to_string())
+        .collect();
+
+    let index = metaculus.create_index_from_questions(ids.clone(), vec![1.0, 1.0, 10.0]);
+    assert_eq!(index.questions.len(), 2);
+    assert_eq!(index.questions[1].get_value(), 1.132);
+
+    // The deprecated constructor ignores ids without a weight, as it always has
+    let index = metaculus.create_index_from_questions(ids.clone(), vec![1.0]);
+    assert_eq!(index.questions.len(), 1);
+    assert_eq!(index.questions[0].question.id, 1493);
+    assert!(metaculus
+        .try_create_index_from_questions(ids, vec![1.0])
+        .is_err());
+}
+
+#[test]
+fn test_index_build_report() {
+    let metaculus = fixture_client();
+    let ids: Vec<String> = vec!["1493", "1", "956", "7887"]
+        .into_iter()
+        .map(|id| id.to_string())
+        .collect();
+
+    let report = metaculus
+        .try_create_index_from_questions(ids.clone(), vec![1.0, 1.0, 1.0, 10.0])
+        .unwrap();
+    assert!(!report.is_complete());
+    assert_eq!(report.index.questions.len(), 2);
+    let skipped: Vec<(&str, &SkipReason)> = report
+        .skipped
+        .iter()
+        .map(|s| (s.id.as_str(), &s.reason))
+        .collect();
+    assert_eq!(
+        skipped,
+        vec![
+            (
+                "1",
+                &SkipReason::Fetch(MetaculusError::NotFound {
+                    id: "1".to_string()
+                })
+            ),
+            ("956", &SkipReason::UnsupportedType),
+        ]
+    );
+
+    assert!(matches!(
+        metaculus.try_create_index_from_questions(ids, vec![1.0, 1.0]),
+        Err(MetaculusError::InvalidIndex(_))
+    ));
+}
+
+const DEFINITION_JSON: &str = r#"{
+    "name": "Test index",
+    "description": "An index of the test fixtures",
+    "questions": [
+        {"id": 8541, "weight": 1.0},
+        {"id": 7887, "weight": 10.0, "zero": 0.1},
+        {"id": 2646, "weight": 1.0, "linearise_if_log": false},
+        {"id": 1, "weight": 1.0}
+    ]
+}"#;
+
+#[test]
+fn test_index_definition() {
+    let definition = IndexDefinition::from_json(DEFINITION_JSON).unwrap();
+    assert_eq!(definition.name, "Test index");
+    assert_eq!(
+        definition.questions[1],
+        QuestionDefinition {
+            id: 7887,
+            weight: 10.0,
+            zero: Some(0.1),
+            linearise_if_log: None,
+        }
+    );
+
+    // Question 1 does not exist, so it is left out
+    let report = fixture_client()
+        .create_index_from_definition(&definition)
+        .unwrap();
+    assert_eq!(
+        report.skipped,
+        vec![SkippedQuestion {
+            id: "1".to_string(),
+            reason: SkipReason::Fetch(MetaculusError::NotFound {
+                id: "1".to_string()
+            }),
+        }]
+    );
+    let index = report.index;
+    assert_eq!(index.questions.len(), 3);
+    assert_eq!(index.questions[1].zero, 0.1);
+    assert!(index.questions[1].linearise_if_log);
+    assert!(!index.questions[2].linearise_if_log);
+
+    let mut range =
+        WeightedQuestion::create_from_range(&read_q_from_file("range_example"), 10.0).unwrap();
+    range.zero = 0.1;
+    assert_eq!(index.questions[1].get_value(), range.get_value());
+
+    // Definitions round-trip through serde
+    let json = serde_json::to_string(&definition).unwrap();
+    assert_eq!(IndexDefinition::from_json(&json).unwrap(), definition);
+
+    match IndexDefinition::from_json(r#"{"name": "Bad", "questions": [{"id": "x"}]}"#) {
+        Err(MetaculusError::Deserialize { path, .. }) => assert_eq!(path, "questions[0].id"),
+        _ => panic!("expected a deserialization error"),
+    }
+}
+
+fn offline_client(base_url: &str, expected_url: &'static str) -> Metaculus {
+    Metaculus::builder()
+        .base_url(base_url)
+        .transport(
+            move |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                assert_eq!(request.url, expected_url);
+                Err(MetaculusError::Transport("offline".to_string()))
+            },
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_index_definition_domain() {
+    let mut definition = IndexDefinition {
+        name: "Pandemic".to_string(),
+        description: String::new(),
+        domain: Some("pandemic".to_string()),
+        questions: vec![QuestionDefinition {
+            id: 8541,
+            weight: 1.0,
+            zero: None,
+            linearise_if_log: None,
+        }],
+    };
+
+    // Only the subdomain of the base URL is replaced
+    for (base_url, expected_url) in [
+        (
+            "https://www.metaculus.com",
+            "https://pandemic.metaculus.com/api2/questions/8541",
+        ),
+        (
+            "http://www.metaculus.com:8080/mirror",
+            "http://pandemic.metaculus.com:8080/mirror/api2/questions/8541",
+        ),
+    ] {
+        let report = offline_client(base_url, expected_url)
+            .create_index_from_definition(&definition)
+            .unwrap();
+        assert!(report.index.questions.is_empty());
+    }
+
+    // Hostnames are case-insensitive
+    definition.domain = Some("Pandemic".to_string());
+    let report = offline_client(
+        "https://www.metaculus.com",
+        "https://pandemic.metaculus.com/api2/questions/8541",
+    )
+    .create_index_from_definition(&definition)
+    .unwrap();
+    assert!(report.index.questions.is_empty());
+
+    // A base URL without a subdomain has nowhere to put the domain
+    for base_url in ["http://fixtures", "http://127.0.0.1:8080"] {
+        assert!(matches!(
+            offline_client(base_url, "unreachable").create_index_from_definition(&definition),
+            Err(MetaculusError::InvalidDomain(_))
+        ));
+    }
+
+    // Anything but a single DNS label could point the client at another host
+    for domain in ["evil.example/x#", "evil.example?", "evil example", ""] {
+        definition.domain = Some(domain.to_string());
+        assert!(matches!(
+            offline_client("https://www.metaculus.com", "unreachable")
+                .create_index_from_definition(&definition),
+            Err(MetaculusError::InvalidDomain(_))
+        ));
+    }
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_index_definition_toml() {
+    let definition = IndexDefinition::from_toml(
+        r#"
+        name = "Test index"
+        description = "An index of the test fixtures"
+
+        [[questions]]
+        id = 8541
+        weight = 1.0
+
+        [[questions]]
+        id = 7887
+        weight = 10.0
+        zero = 0.1
+
+        [[questions]]
+        id = 2646
+        weight = 1.0
+        linearise_if_log = false
+
+        [[questions]]
+        id = 1
+        weight = 1.0
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        definition,
+        IndexDefinition::from_json(DEFINITION_JSON).unwrap()
+    );
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_index_definition_yaml() {
+    let definition = IndexDefinition::from_yaml(
+        r#"
+name: Test index
+description: An index of the test fixtures
+questions:
+  - id: 8541
+    weight: 1.0
+  - id: 7887
+    weight: 10.0
+    zero: 0.1
+  - id: 2646
+    weight: 1.0
+    linearise_if_log: false
+  - id: 1
+    weight: 1.0
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        definition,
+        IndexDefinition::from_json(DEFINITION_JSON).unwrap()
+    );
+}
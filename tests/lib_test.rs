@@ -1,7 +1,12 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{DateTime, NaiveDate};
 
-use crate::utils::read_q_from_file;
-use metaculustetra::Prediction::{AmbP, DatP, NumP};
+use crate::utils::{fixture_client, read_edited_q_from_file, read_q_from_file};
+use metaculustetra::Prediction::{
+    AboveUpper, AmbP, Annulled, BelowLower, DatP, MultipleChoice, NumP,
+};
+use metaculustetra::{
+    Project, PublicationStatus, Quartiles, Question, QuestionKind, QuestionStatus,
+};
 
 mod utils;
 
@@ -57,6 +62,28 @@ fn test_resolved_range_question() {
     );
 }
 
+#[test]
+fn test_out_of_bounds_resolution() {
+    let above = read_edited_q_from_file("resolved_range_example", |json| {
+        json["resolution"] = 1.2.into();
+    });
+    assert_eq!(above.get_resolution(), Some(AboveUpper));
+    assert_eq!(above.get_best_prediction(), Some(AboveUpper));
+    assert_eq!(above.normalise(&AboveUpper), Some(1.0));
+
+    let below = read_edited_q_from_file("resolved_range_example", |json| {
+        json["resolution"] = (-0.3).into();
+    });
+    assert_eq!(below.get_resolution(), Some(BelowLower));
+
+    // A closed bound cannot be resolved beyond, so the resolution is taken to be at the bound
+    let closed = read_edited_q_from_file("resolved_range_example", |json| {
+        json["resolution"] = (-0.3).into();
+        json["possibilities"]["low"] = false.into();
+    });
+    assert_eq!(closed.get_resolution(), Some(NumP(-20.0)));
+}
+
 #[test]
 fn test_range_question() {
     let question = read_q_from_file("range_example");
@@ -71,7 +98,7 @@ fn test_logarithmic_range_question() {
     let question = read_q_from_file("logarithmic_range_example");
 
     let community_prediction =
-        ((100000000000000000000000000.0 / 1000000000000.0) as f64).powf(0.41079) * 1000000000000.0;
+        (100000000000000000000000000.0_f64 / 1000000000000.0).powf(0.41079) * 1000000000000.0;
 
     assert_eq!(
         question.get_best_prediction().unwrap(),
@@ -91,18 +118,19 @@ fn test_date_range_question() {
         .unwrap()
         .and_hms_opt(0, 0, 0)
         .unwrap()
+        .and_utc()
         .timestamp() as f64;
     let end_date = NaiveDate::parse_from_str("2025-01-01", "%Y-%m-%d")
         .unwrap()
         .and_hms_opt(0, 0, 0)
         .unwrap()
+        .and_utc()
         .timestamp() as f64;
 
-    let community_date = NaiveDateTime::from_timestamp_opt(
-        (0.27891 * (end_date - start_date) + start_date) as i64,
-        0,
-    )
-    .unwrap();
+    let community_date =
+        DateTime::from_timestamp((0.27891 * (end_date - start_date) + start_date) as i64, 0)
+            .unwrap()
+            .naive_utc();
 
     assert_eq!(
         question.get_best_prediction().unwrap(),
@@ -122,18 +150,21 @@ fn test_logarithmic_date_range_question() {
         .unwrap()
         .and_hms_opt(0, 0, 0)
         .unwrap()
+        .and_utc()
         .timestamp() as f64;
     let end_date = NaiveDate::parse_from_str("2200-01-04", "%Y-%m-%d")
         .unwrap()
         .and_hms_opt(0, 0, 0)
         .unwrap()
+        .and_utc()
         .timestamp() as f64;
 
-    let community_date = NaiveDateTime::from_timestamp_opt(
+    let community_date = DateTime::from_timestamp(
         ((end_date / start_date).powf(0.70277) * start_date) as i64,
         0,
     )
-    .unwrap();
+    .unwrap()
+    .naive_utc();
 
     assert_eq!(
         question.get_best_prediction().unwrap(),
@@ -175,6 +206,35 @@ fn test_ambiguously_resolved_range_question() {
     assert_eq!(question.get_metaculus_prediction().unwrap(), NumP(38.73));
 }
 
+#[test]
+fn test_annulled_question() {
+    let binary = read_edited_q_from_file("resolved_probability_example", |json| {
+        json["resolution"] = (-2.0).into();
+    });
+    assert_eq!(binary.get_resolution(), Some(Annulled));
+    assert_eq!(binary.get_best_prediction(), Some(Annulled));
+    assert_eq!(binary.status(), QuestionStatus::Annulled);
+
+    // Ambiguous resolutions are kept apart from annulled ones
+    let ambiguous = read_edited_q_from_file("resolved_probability_example", |json| {
+        json["resolution"] = (-1.0).into();
+    });
+    assert_eq!(ambiguous.get_resolution(), Some(AmbP));
+    assert_eq!(ambiguous.status(), QuestionStatus::Ambiguous);
+
+    // Continuous questions use the same codes, even though they lie beyond the scale
+    let continuous = read_edited_q_from_file("resolved_range_example", |json| {
+        json["resolution"] = (-2.0).into();
+    });
+    assert_eq!(continuous.get_resolution(), Some(Annulled));
+
+    // Codes this crate does not know about are not mistaken for probabilities
+    let unknown = read_edited_q_from_file("resolved_probability_example", |json| {
+        json["resolution"] = (-3.0).into();
+    });
+    assert_eq!(unknown.get_resolution(), None);
+}
+
 #[test]
 fn test_unrevealed_question() {
     let question = read_q_from_file("tournament_example");
@@ -186,7 +246,7 @@ fn test_unrevealed_question() {
 fn test_get_if() {
     let ambiguous_prediction = AmbP;
     let date_prediction = DatP(
-        NaiveDate::from_ymd_opt(1954, 03, 02)
+        NaiveDate::from_ymd_opt(1954, 3, 2)
             .unwrap()
             .and_hms_opt(0, 0, 0)
             .unwrap(),
@@ -200,7 +260,7 @@ fn test_get_if() {
     assert_eq!(
         date_prediction.get_if_date(),
         Some(
-            NaiveDate::from_ymd_opt(1954, 03, 02)
+            NaiveDate::from_ymd_opt(1954, 3, 2)
                 .unwrap()
                 .and_hms_opt(0, 0, 0)
                 .unwrap()
@@ -257,3 +317,303 @@ fn test_get_community_prediction_before() {
         NumP(0.2)
     );
 }
+
+#[test]
+fn test_normalise() {
+    let linear = read_q_from_file("resolved_range_example");
+    assert_eq!(linear.normalise(&NumP(5.0)), Some(0.5));
+    assert_eq!(linear.normalise(&DatP(NaiveDate::default().into())), None);
+
+    let logarithmic = read_q_from_file("logarithmic_range_example");
+    assert!((logarithmic.normalise(&NumP(1e19)).unwrap() - 0.5).abs() < 1e-12);
+
+    let date = read_q_from_file("date_range_example");
+    assert_eq!(
+        date.normalise(&DatP(
+            NaiveDate::from_ymd_opt(2021, 1, 15)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        )),
+        Some(0.0)
+    );
+    assert!(!date.has_open_lower_bound());
+    assert!(date.has_open_upper_bound());
+
+    let binary = read_q_from_file("probability_example");
+    assert_eq!(binary.normalise(&NumP(0.5)), None);
+}
+
+#[test]
+fn test_quartiles() {
+    let binary = read_q_from_file("probability_example");
+    assert_eq!(
+        binary.get_community_quartiles().unwrap(),
+        Quartiles {
+            lower: NumP(0.1),
+            median: NumP(0.2),
+            upper: NumP(0.33),
+        }
+    );
+    assert_eq!(binary.get_metaculus_quartiles(), None);
+
+    let range = read_q_from_file("resolved_range_example");
+    let community = range.get_community_quartiles().unwrap();
+    assert_eq!(community.median, NumP(0.49337 * 50.0 - 20.0));
+    assert_eq!(community.lower, NumP(0.46304 * 50.0 - 20.0));
+    assert_eq!(community.upper, NumP(0.52384 * 50.0 - 20.0));
+
+    let metaculus = range.get_metaculus_quartiles().unwrap();
+    assert_eq!(metaculus.lower, NumP(0.47171 * 50.0 - 20.0));
+    assert_eq!(metaculus.median, range.get_metaculus_prediction().unwrap());
+    assert_eq!(
+        range.get_metaculus_quartiles_before(
+            NaiveDate::from_ymd_opt(1945, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_metadata() {
+    let question = read_q_from_file("tournament_example");
+    assert_eq!(question.id, 8541);
+    assert_eq!(question.title_short, "US rejoin Iran Nuclear Deal by 2024?");
+    assert_eq!(question.status, PublicationStatus::Approved);
+    assert_eq!(question.kind, QuestionKind::Forecast);
+    assert_eq!(
+        question.close_time,
+        Some(
+            NaiveDate::from_ymd_opt(2023, 12, 31)
+                .unwrap()
+                .and_hms_opt(22, 59, 0)
+                .unwrap()
+        )
+    );
+    assert!(question.created_time.unwrap() < question.publish_time.unwrap());
+    assert!(question
+        .categories
+        .contains(&"series--nuclearthreats".to_string()));
+    assert_eq!(question.projects.len(), 2);
+
+    let discussion = read_q_from_file("discussion_example");
+    assert_eq!(discussion.kind, QuestionKind::Discussion);
+    assert_eq!(
+        discussion.projects,
+        vec![Project {
+            id: 144,
+            name: "Metaculus".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_malformed_metadata() {
+    // Metadata in unexpected shapes is left unknown rather than failing the whole question
+    let question = read_q_from_file("malformed_metadata_example");
+    assert_eq!(question.id, 90002);
+    assert_eq!(question.title, "");
+    assert_eq!(question.created_time, None);
+    assert_eq!(question.publish_time, None);
+    assert_eq!(question.close_time, None);
+    assert_eq!(
+        question.resolve_time,
+        Some(
+            NaiveDate::from_ymd_opt(2021, 11, 8)
+                .unwrap()
+                .and_hms_opt(22, 0, 0)
+                .unwrap()
+        )
+    );
+    assert_eq!(question.status, PublicationStatus::default());
+    assert!(question.categories.is_empty());
+    assert_eq!(
+        question.projects,
+        vec![
+            Project {
+                id: 1005,
+                name: String::new()
+            },
+            Project {
+                id: 0,
+                name: String::new()
+            },
+        ]
+    );
+    assert_eq!((question.comment_count, question.votes), (0, 0));
+    assert_eq!(question.get_resolution(), Some(NumP(1.0)));
+}
+
+#[test]
+fn test_status_at() {
+    let date = |y, m, d| {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    };
+
+    let question = read_q_from_file("resolved_range_example");
+    assert_eq!(
+        question.status_at(date(2021, 1, 1)),
+        QuestionStatus::Upcoming
+    );
+    assert_eq!(question.status_at(date(2021, 7, 1)), QuestionStatus::Open);
+    assert_eq!(
+        question.status_at(date(2021, 10, 28)),
+        QuestionStatus::Closed
+    );
+    assert_eq!(
+        question.status_at(date(2021, 11, 1)),
+        QuestionStatus::Resolved
+    );
+    assert_eq!(question.status(), QuestionStatus::Resolved);
+
+    let ambiguous = read_q_from_file("ambiguously_resolved_example");
+    assert_eq!(
+        ambiguous.status_at(date(2021, 11, 2)),
+        QuestionStatus::Ambiguous
+    );
+
+    let unresolved = read_q_from_file("date_range_example");
+    assert_eq!(
+        unresolved.status_at(date(2025, 1, 1)),
+        QuestionStatus::Closed
+    );
+}
+
+#[test]
+fn test_predictions_stop_at_close() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string("tests/probability_example.json").unwrap())
+            .unwrap();
+    json["close_time"] = "2019-03-06T00:00:00Z".into();
+    let question: Question = serde_json::from_value(json).unwrap();
+
+    assert_eq!(question.get_community_prediction(), Some(NumP(0.38)));
+    assert_eq!(
+        question.get_community_quartiles().unwrap().median,
+        NumP(0.38)
+    );
+}
+
+/// Makes a group question out of the fixtures with the given names and labels.
+fn group_of(options: &[(&str, &str)]) -> Question {
+    let sub_questions: Vec<serde_json::Value> = options
+        .iter()
+        .map(|(name, label)| {
+            let mut json: serde_json::Value = serde_json::from_str(
+                &std::fs::read_to_string(format!("tests/{}.json", name)).unwrap(),
+            )
+            .unwrap();
+            json["sub_question_label"] = (*label).into();
+            json
+        })
+        .collect();
+
+    serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "title_short": "Group",
+        "type": "group",
+        "possibilities": {"type": "group"},
+        "prediction_timeseries": null,
+        "metaculus_prediction": null,
+        "resolution": null,
+        "sub_questions": sub_questions,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_group_question() {
+    let group = group_of(&[
+        ("probability_example", "Ragnarök"),
+        ("tournament_example", "Iran deal"),
+    ]);
+    assert!(group.is_group());
+    assert_eq!(group.kind, QuestionKind::Group);
+    assert_eq!(group.get_resolution(), None);
+
+    let prediction = group.get_community_prediction().unwrap();
+    assert_eq!(
+        prediction,
+        MultipleChoice(vec![
+            ("Ragnarök".to_string(), 0.2),
+            ("Iran deal".to_string(), 0.3)
+        ])
+    );
+    assert_eq!(prediction.get_if_multiple_choice().unwrap().len(), 2);
+    assert_eq!(prediction.get_if_numeric(), None);
+
+    // Before any option was published
+    assert_eq!(
+        group.get_best_prediction_before(
+            NaiveDate::from_ymd_opt(2018, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_group_question_with_unrepresentable_option() {
+    // A continuous sub-question has no probability to put in the options
+    let group = group_of(&[
+        ("probability_example", "Ragnarök"),
+        ("range_example", "GDP"),
+    ]);
+    assert_eq!(group.get_community_prediction(), None);
+    assert_eq!(group.get_best_prediction(), None);
+}
+
+#[test]
+fn test_resolved_group_question() {
+    let group = group_of(&[
+        ("resolved_probability_example", "Infrastructure"),
+        ("ambiguously_resolved_example", "Cryonics"),
+    ]);
+    // One option resolved ambiguously, so the group has no resolution
+    assert_eq!(group.get_resolution(), None);
+
+    let group = group_of(&[("resolved_probability_example", "Infrastructure")]);
+    assert_eq!(
+        group.get_best_prediction(),
+        Some(MultipleChoice(vec![("Infrastructure".to_string(), 1.0)]))
+    );
+}
+
+#[test]
+fn test_fetched_group_question() {
+    let metaculus = fixture_client();
+    let group = metaculus.get_question("90006").unwrap();
+    assert_eq!(group.kind, QuestionKind::Group);
+    assert_eq!(group.sub_questions.len(), 2);
+
+    assert_eq!(
+        group.get_community_prediction(),
+        Some(MultipleChoice(vec![
+            ("Lab A".to_string(), 0.7),
+            ("Lab B".to_string(), 0.3)
+        ]))
+    );
+    assert_eq!(
+        group.get_community_prediction_before(
+            NaiveDate::from_ymd_opt(2022, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        ),
+        Some(MultipleChoice(vec![
+            ("Lab A".to_string(), 0.4),
+            ("Lab B".to_string(), 0.6)
+        ]))
+    );
+    let resolution = MultipleChoice(vec![("Lab A".to_string(), 1.0), ("Lab B".to_string(), 0.0)]);
+    assert_eq!(group.get_resolution(), Some(resolution.clone()));
+    assert_eq!(metaculus.get_prediction_for("90006"), Some(resolution));
+}
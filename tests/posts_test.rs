@@ -0,0 +1,224 @@
+mod utils;
+
+use chrono::NaiveDate;
+use metaculustetra::conditional::ConditionalQuestion;
+use metaculustetra::transport::{HttpRequest, HttpResponse};
+use metaculustetra::Prediction::{BelowLower, MultipleChoice, NumP};
+use metaculustetra::{
+    ApiVersion, Metaculus, MetaculusError, Project, PublicationStatus, Quartiles, Question,
+    QuestionKind,
+};
+
+use crate::utils::{assert_close, fixture_client};
+
+fn read_post(filename: &str) -> Question {
+    let body = std::fs::read_to_string(format!("tests/{}.json", filename)).unwrap();
+    Question::from_json(&body, ApiVersion::Posts).unwrap()
+}
+
+#[test]
+fn test_detect_api_version() {
+    let legacy: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string("tests/probability_example.json").unwrap())
+            .unwrap();
+    let post: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string("tests/posts_probability_example.json").unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(ApiVersion::detect(&legacy), ApiVersion::Legacy);
+    assert_eq!(ApiVersion::detect(&post), ApiVersion::Posts);
+}
+
+#[test]
+fn test_posts_probability_question() {
+    // Served at the legacy URL, so the client has to detect that it is a post
+    let question = fixture_client().get_question("30001").unwrap();
+
+    assert_eq!(question.id, 30001);
+    assert_eq!(question.title_short, "Test passes by 2024");
+    assert_eq!(question.page_url, "/questions/30001/test-passes/");
+    assert_eq!(question.status, PublicationStatus::Approved);
+    assert_eq!(question.kind, QuestionKind::Forecast);
+    assert_eq!(question.categories, vec!["computing-and-math"]);
+    assert_eq!(
+        question.projects,
+        vec![Project {
+            id: 3349,
+            name: "Quarterly Cup".to_string()
+        }]
+    );
+    assert_eq!((question.comment_count, question.votes), (12, 34));
+    assert_eq!(question.number_of_predictions, 57);
+
+    assert!(question.is_binary());
+    assert_eq!(question.get_resolution(), Some(NumP(1.0)));
+    assert_eq!(question.get_community_prediction(), Some(NumP(0.7)));
+    assert_eq!(question.get_metaculus_prediction(), Some(NumP(0.6)));
+    assert_eq!(
+        question.get_community_quartiles(),
+        Some(Quartiles {
+            lower: NumP(0.6),
+            median: NumP(0.7),
+            upper: NumP(0.8),
+        })
+    );
+
+    let second_day = NaiveDate::from_ymd_opt(2024, 1, 3)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+    assert_eq!(
+        question.get_community_prediction_before(second_day),
+        Some(NumP(0.55))
+    );
+    let counts: Vec<Option<u64>> = question
+        .community_timeseries()
+        .map(|point| point.num_predictions)
+        .collect();
+    assert_eq!(counts, vec![Some(3), Some(8), Some(12)]);
+}
+
+#[test]
+fn test_posts_range_question() {
+    let question = read_post("posts_range_example");
+
+    assert!(!question.is_binary());
+    assert!(question.is_logarithmic());
+    assert!(question.has_open_lower_bound());
+    assert!(!question.has_open_upper_bound());
+    assert_close(
+        question.get_resolution().unwrap().get_if_numeric().unwrap(),
+        100.0,
+        1e-9,
+    );
+    assert_close(
+        question
+            .get_community_prediction()
+            .unwrap()
+            .get_if_numeric()
+            .unwrap(),
+        1000.0_f64.sqrt(),
+        1e-9,
+    );
+
+    let distribution = question.get_community_distribution().unwrap();
+    assert_close(distribution.mass_below_lower_bound(), 0.02183, 1e-9);
+    let median = distribution.median().unwrap().get_if_numeric().unwrap();
+    assert!((median - 1000.0_f64.sqrt()).abs() < 0.5);
+
+    let body = std::fs::read_to_string("tests/posts_range_example.json").unwrap();
+    let below = body.replace("\"100.0\"", "\"below_lower_bound\"");
+    let below = Question::from_json(&below, ApiVersion::Posts).unwrap();
+    assert_eq!(below.get_resolution(), Some(BelowLower));
+    // The upper bound is closed
+    let above = body.replace("\"100.0\"", "\"above_upper_bound\"");
+    let above = Question::from_json(&above, ApiVersion::Posts).unwrap();
+    assert_eq!(above.get_resolution(), Some(NumP(1000.0)));
+}
+
+#[test]
+fn test_posts_multiple_choice_question() {
+    let question = read_post("posts_multiple_choice_example");
+
+    assert!(question.is_group());
+    assert_eq!(
+        question.get_community_prediction(),
+        Some(MultipleChoice(vec![
+            ("Unit".to_string(), 0.3),
+            ("Integration".to_string(), 0.6),
+            ("Doc".to_string(), 0.1),
+        ]))
+    );
+    assert_eq!(
+        question.get_resolution(),
+        Some(MultipleChoice(vec![
+            ("Unit".to_string(), 0.0),
+            ("Integration".to_string(), 1.0),
+            ("Doc".to_string(), 0.0),
+        ]))
+    );
+
+    let ids: Vec<u64> = question.sub_questions.iter().map(|q| q.id).collect();
+    assert!(ids.iter().all(|&id| id != question.id));
+    assert_ne!(ids[0], ids[1]);
+    assert_ne!(ids[1], ids[2]);
+    assert_ne!(ids[0], ids[2]);
+    assert_eq!(
+        read_post("posts_multiple_choice_example").sub_questions[1].id,
+        ids[1]
+    );
+}
+
+#[test]
+fn test_posts_group_and_conditional() {
+    let post: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string("tests/posts_probability_example.json").unwrap(),
+    )
+    .unwrap();
+    let mut yes = post["question"].clone();
+    yes["label"] = "Yes branch".into();
+    let mut no = post["question"].clone();
+    no["resolution"] = "annulled".into();
+
+    let group = serde_json::json!({
+        "id": 30004,
+        "title": "Group",
+        "group_of_questions": {"questions": [yes, no]},
+    });
+    let question = Question::from_json(&group.to_string(), ApiVersion::Auto).unwrap();
+    assert_eq!(question.kind, QuestionKind::Group);
+    assert_eq!(question.sub_questions.len(), 2);
+    assert_eq!(
+        question.sub_questions[0].sub_question_label.as_deref(),
+        Some("Yes branch")
+    );
+
+    let conditional = serde_json::json!({
+        "id": 30005,
+        "conditional": {
+            "condition": yes,
+            "condition_child": no,
+            "question_yes": yes,
+            "question_no": no,
+        },
+    });
+    let conditional =
+        ConditionalQuestion::from_json(&conditional.to_string(), ApiVersion::Auto).unwrap();
+    assert_eq!(conditional.id, 30005);
+    assert_eq!(conditional.get_probability_if_yes(), Some(1.0));
+
+    // A group is not a conditional pair
+    assert!(matches!(
+        ConditionalQuestion::from_json(&group.to_string(), ApiVersion::Posts),
+        Err(MetaculusError::Deserialize { .. })
+    ));
+}
+
+#[test]
+fn test_posts_api_version() {
+    let body = std::fs::read_to_string("tests/posts_probability_example.json").unwrap();
+    let metaculus = Metaculus::builder()
+        .base_url("http://fixtures")
+        .api_version(ApiVersion::Posts)
+        .transport(
+            move |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                assert_eq!(request.url, "http://fixtures/api/posts/30001/");
+                Ok(HttpResponse {
+                    status: 200,
+                    body: body.clone(),
+                })
+            },
+        )
+        .build()
+        .unwrap();
+    assert_eq!(metaculus.api_version(), ApiVersion::Posts);
+    assert_eq!(metaculus.get_prediction_for("30001"), Some(NumP(1.0)));
+
+    // The legacy API has no posts, so a post fails to deserialize as a legacy question
+    let body = std::fs::read_to_string("tests/posts_probability_example.json").unwrap();
+    assert!(matches!(
+        Question::from_json(&body, ApiVersion::Legacy),
+        Err(MetaculusError::Deserialize { .. })
+    ));
+}
@@ -0,0 +1,192 @@
+use metaculustetra::query::QuestionQuery;
+use metaculustetra::transport::{HttpRequest, HttpResponse};
+use metaculustetra::{ApiVersion, Metaculus, MetaculusError, Question};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn page(fixtures: &[&str], next: Option<&str>) -> String {
+    let results: Vec<serde_json::Value> = fixtures
+        .iter()
+        .map(|f| {
+            serde_json::from_str(&std::fs::read_to_string(format!("tests/{}.json", f)).unwrap())
+                .unwrap()
+        })
+        .collect();
+    serde_json::json!({
+        "count": 3,
+        "next": next,
+        "previous": null,
+        "results": results,
+    })
+    .to_string()
+}
+
+fn paginated_client(requests: Arc<AtomicUsize>) -> Metaculus {
+    Metaculus::builder()
+        .base_url("http://fixtures/")
+        .transport(
+            move |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let body = match request.url.as_str() {
+                    "http://fixtures/api2/questions/?search=nuclear+risk&project=1007" => page(
+                        &["probability_example", "range_example"],
+                        Some("http://fixtures/api2/questions/?search=nuclear+risk&project=1007&offset=2"),
+                    ),
+                    "http://fixtures/api2/questions/?search=nuclear+risk&project=1007&offset=2" => {
+                        page(&["tournament_example"], None)
+                    }
+                    _ => {
+                        return Ok(HttpResponse {
+                            status: 503,
+                            body: "Service unavailable".to_string(),
+                        })
+                    }
+                };
+                Ok(HttpResponse { status: 200, body })
+            },
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_query_string() {
+    assert_eq!(QuestionQuery::new().to_query_string(), "");
+    assert_eq!(
+        QuestionQuery::new()
+            .search("AI & compute")
+            .category("computing--ai")
+            .category("tech--space")
+            .page_size(20)
+            .to_query_string(),
+        "search=AI+%26+compute&categories=computing--ai%2Ctech--space&limit=20"
+    );
+}
+
+#[test]
+fn test_list_questions_follows_pages() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let metaculus = paginated_client(requests.clone());
+    let query = QuestionQuery::new().search("nuclear risk").project("1007");
+
+    let titles: Vec<String> = metaculus
+        .list_questions(&query)
+        .map(|q| q.unwrap().title_short)
+        .collect();
+
+    assert_eq!(
+        titles,
+        vec![
+            "Global population to fall by >10% by 2100?",
+            "EA Wiki: fraction of EA Forum traffic",
+            "US rejoin Iran Nuclear Deal by 2024?",
+        ]
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_list_questions_is_lazy() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let metaculus = paginated_client(requests.clone());
+    let query = QuestionQuery::new().search("nuclear risk").project("1007");
+
+    assert_eq!(metaculus.list_questions(&query).take(2).count(), 2);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_list_questions_error_ends_iteration() {
+    let metaculus = paginated_client(Arc::new(AtomicUsize::new(0)));
+    let mut questions = metaculus.list_questions(&QuestionQuery::new().status("open"));
+
+    assert!(matches!(
+        questions.next(),
+        Some(Err(MetaculusError::Status { status: 503, .. }))
+    ));
+    assert!(questions.next().is_none());
+}
+
+#[test]
+fn test_list_questions_stays_on_base_url() {
+    let urls = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = urls.clone();
+    let metaculus = Metaculus::builder()
+        .base_url("http://fixtures")
+        .token("secret")
+        .transport(
+            move |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                recorded.lock().unwrap().push(request.url.clone());
+                let body = match request.url.as_str() {
+                    "http://fixtures/api2/questions/?" => page(
+                        &["probability_example"],
+                        Some("https://evil.example/api2/questions/?offset=1"),
+                    ),
+                    "http://fixtures/api2/questions/?offset=1" => page(
+                        &["range_example"],
+                        Some("http://fixtures:8080/api2/questions/?offset=2"),
+                    ),
+                    "http://fixtures/api2/questions/?offset=2" => {
+                        page(&["tournament_example"], None)
+                    }
+                    _ => panic!("unexpected request to {}", request.url),
+                };
+                Ok(HttpResponse { status: 200, body })
+            },
+        )
+        .build()
+        .unwrap();
+
+    let questions: Vec<Question> = metaculus
+        .list_questions(&QuestionQuery::new())
+        .map(|q| q.unwrap())
+        .collect();
+
+    // The token is never sent to another host or port
+    assert_eq!(questions.len(), 3);
+    assert_eq!(
+        *urls.lock().unwrap(),
+        vec![
+            "http://fixtures/api2/questions/?",
+            "http://fixtures/api2/questions/?offset=1",
+            "http://fixtures/api2/questions/?offset=2",
+        ]
+    );
+}
+
+#[test]
+fn test_list_posts_query() {
+    let metaculus = Metaculus::builder()
+        .base_url("http://fixtures")
+        .api_version(ApiVersion::Posts)
+        .transport(
+            |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                assert_eq!(
+                    request.url,
+                    "http://fixtures/api/posts/?search=cup&tournaments=3349&statuses=resolved\
+                     &categories=computing-and-math&categories=technology\
+                     &order_by=-published_at&limit=5"
+                );
+                Ok(HttpResponse {
+                    status: 200,
+                    body: page(&["posts_probability_example"], None),
+                })
+            },
+        )
+        .build()
+        .unwrap();
+    let query = QuestionQuery::new()
+        .search("cup")
+        .project("3349")
+        .status("resolved")
+        .category("computing-and-math")
+        .category("technology")
+        .order_by("-publish_time")
+        .page_size(5);
+
+    let ids: Vec<u64> = metaculus
+        .list_questions(&query)
+        .map(|q| q.unwrap().id)
+        .collect();
+    assert_eq!(ids, vec![30001]);
+}
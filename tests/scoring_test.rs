@@ -0,0 +1,141 @@
+mod utils;
+
+use crate::utils::{assert_close, read_edited_q_from_file, read_q_from_file};
+use metaculustetra::scoring::{final_scores, time_averaged_scores, Aggregate, Scores};
+
+#[test]
+fn test_binary_scores() {
+    let question = read_q_from_file("resolved_probability_example");
+
+    match final_scores(&question, Aggregate::Community).unwrap() {
+        Scores::Binary { brier, log } => {
+            assert_close(brier, 0.0001, 1e-12);
+            assert_close(log, 0.99_f64.ln(), 1e-12);
+        }
+        Scores::Continuous { .. } => panic!("expected binary scores"),
+    }
+
+    // Weighted by how long each community prediction stood before the question resolved
+    match time_averaged_scores(&question, Aggregate::Community).unwrap() {
+        Scores::Binary { brier, .. } => assert_close(brier, 0.092447, 1e-6),
+        Scores::Continuous { .. } => panic!("expected binary scores"),
+    }
+
+    let metaculus = time_averaged_scores(&question, Aggregate::Metaculus).unwrap();
+    assert!(metaculus.log() < 0.0);
+}
+
+fn assert_continuous(scores: Scores, expected_log: f64, expected_crps: f64) {
+    match scores {
+        Scores::Continuous { log, crps } => {
+            assert_close(log, expected_log, 1e-6);
+            assert_close(crps, expected_crps, 1e-6);
+        }
+        Scores::Binary { .. } => panic!("expected continuous scores"),
+    }
+}
+
+#[test]
+fn test_continuous_scores() {
+    let question = read_q_from_file("resolved_range_example");
+
+    // Nothing was predicted after the question closed, so the full distributions are scored
+    assert_continuous(
+        final_scores(&question, Aggregate::Community).unwrap(),
+        1.231429,
+        0.033683,
+    );
+    assert_continuous(
+        final_scores(&question, Aggregate::Metaculus).unwrap(),
+        0.962698,
+        0.035772,
+    );
+    assert_continuous(
+        time_averaged_scores(&question, Aggregate::Community).unwrap(),
+        0.912207,
+        0.052769,
+    );
+    assert_continuous(
+        time_averaged_scores(&question, Aggregate::Metaculus).unwrap(),
+        0.305463,
+        0.057300,
+    );
+}
+
+#[test]
+fn test_continuous_scores_ignore_predictions_after_close() {
+    // Predictions made at resolution, right on the answer
+    let question = read_edited_q_from_file("resolved_range_example", |json| {
+        json["prediction_timeseries"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({
+                "t": 1635424200.0,
+                "community_prediction": {"q1": 0.43, "q2": 0.44, "q3": 0.45},
+                "num_predictions": 300,
+                "distribution": {"num": 49, "s": 0.005, "x0": 0.44, "low": 0.0, "high": 1.0},
+            }));
+        json["metaculus_prediction"]["history"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({
+                "t": 1635424200.0,
+                "x": {"q1": 0.43, "q2": 0.44, "q3": 0.45, "low": 0.0, "high": 1.0},
+            }));
+    });
+
+    // The last distributions before the question closed are scored instead of the full ones
+    assert_continuous(
+        final_scores(&question, Aggregate::Community).unwrap(),
+        1.196269,
+        0.040083,
+    );
+    assert_continuous(
+        final_scores(&question, Aggregate::Metaculus).unwrap(),
+        1.014268,
+        0.036176,
+    );
+}
+
+#[test]
+fn test_continuous_resolution_beyond_bound() {
+    let resolved_at = |resolution: f64, open: bool| {
+        read_edited_q_from_file("resolved_range_example", |json| {
+            json["resolution"] = resolution.into();
+            if !open {
+                json["possibilities"]["low"] = false.into();
+            }
+        })
+    };
+
+    // Beyond a closed bound, the resolution is scored as if it were at the bound
+    let closed = final_scores(&resolved_at(-0.1, false), Aggregate::Community).unwrap();
+    assert!(closed.log().is_finite());
+    assert_eq!(
+        closed,
+        final_scores(&resolved_at(0.0, false), Aggregate::Community).unwrap()
+    );
+
+    // Beyond an open bound, the probability below the scale is scored
+    let question = resolved_at(-0.1, true);
+    let below = question
+        .get_community_distribution()
+        .unwrap()
+        .mass_below_lower_bound();
+    let open = final_scores(&question, Aggregate::Community).unwrap();
+    assert_close(open.log(), below.ln(), 1e-12);
+}
+
+#[test]
+fn test_unscorable_questions() {
+    for name in [
+        "probability_example",
+        "range_example",
+        "ambiguously_resolved_example",
+        "ambiguously_resolved_range_example",
+    ] {
+        let question = read_q_from_file(name);
+        assert_eq!(final_scores(&question, Aggregate::Community), None);
+        assert_eq!(time_averaged_scores(&question, Aggregate::Community), None);
+    }
+}
@@ -0,0 +1,175 @@
+mod utils;
+
+use crate::utils::{fixture_client, fixture_response, fixtures};
+use metaculustetra::distribution::{LogisticComponent, LogisticMixture};
+use metaculustetra::transport::{HttpRequest, HttpResponse, Transport};
+use metaculustetra::{ApiVersion, Metaculus, MetaculusError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+type Posts = Arc<Mutex<Vec<(HttpRequest, serde_json::Value)>>>;
+
+struct RecordingTransport {
+    fixtures: HashMap<String, String>,
+    posts: Posts,
+}
+
+impl Transport for RecordingTransport {
+    fn get(&self, request: &HttpRequest) -> Result<HttpResponse, MetaculusError> {
+        Ok(fixture_response(&self.fixtures, request))
+    }
+
+    fn post(&self, request: &HttpRequest, body: &str) -> Result<HttpResponse, MetaculusError> {
+        self.posts
+            .lock()
+            .unwrap()
+            .push((request.clone(), serde_json::from_str(body).unwrap()));
+        Ok(HttpResponse {
+            status: 201,
+            body: String::new(),
+        })
+    }
+}
+
+fn recording_client() -> (Metaculus, Posts) {
+    let posts = Arc::new(Mutex::new(vec![]));
+    let metaculus = Metaculus::builder()
+        .base_url("http://fixtures")
+        .token("good-token")
+        .transport(RecordingTransport {
+            fixtures: fixtures(),
+            posts: posts.clone(),
+        })
+        .build()
+        .unwrap();
+    (metaculus, posts)
+}
+
+fn logistic(x0: f64, s: f64, w: f64) -> LogisticComponent {
+    LogisticComponent { x0, s, w }
+}
+
+#[test]
+fn test_submit_probability() {
+    let (metaculus, posts) = recording_client();
+
+    metaculus.submit_probability("8541", 0.3).unwrap();
+
+    let posts = posts.lock().unwrap();
+    assert_eq!(
+        posts[0].0.url,
+        "http://fixtures/api2/questions/8541/predict/"
+    );
+    assert!(posts[0]
+        .0
+        .headers
+        .contains(&("Authorization".to_string(), "Token good-token".to_string())));
+    assert_eq!(
+        posts[0].1,
+        serde_json::json!({"prediction": 0.3, "void": false})
+    );
+}
+
+#[test]
+fn test_submit_distribution() {
+    let (metaculus, posts) = recording_client();
+
+    // Closed bounds on both sides
+    metaculus
+        .submit_distribution(
+            "7887",
+            &LogisticMixture::new(vec![logistic(0.2, 0.05, 3.0)]),
+        )
+        .unwrap();
+    // Closed lower bound and open upper bound
+    metaculus
+        .submit_distribution(
+            "6341",
+            &LogisticMixture::new(vec![logistic(0.5, 0.1, 3.0), logistic(0.9, 0.2, 1.0)]),
+        )
+        .unwrap();
+
+    let posts = posts.lock().unwrap();
+    assert_eq!(
+        posts[0].1,
+        serde_json::json!({
+            "prediction": {
+                "kind": "multi",
+                "d": [{"kind": "logistic", "x0": 0.2, "s": 0.05, "w": 1.0, "low": 0.0, "high": 1.0}]
+            },
+            "void": false
+        })
+    );
+
+    let components = posts[1].1["prediction"]["d"].as_array().unwrap();
+    assert_eq!(components[0]["w"], 0.75);
+    assert_eq!(components[0]["low"], 0.0);
+    assert!(
+        (components[0]["high"].as_f64().unwrap() - 1.0 / (1.0 + (-5.0_f64).exp())).abs() < 1e-12
+    );
+    assert_eq!(components[1]["w"], 0.25);
+    assert!(
+        (components[1]["high"].as_f64().unwrap() - 1.0 / (1.0 + (-0.5_f64).exp())).abs() < 1e-12
+    );
+}
+
+#[test]
+fn test_invalid_forecasts_are_not_sent() {
+    let (metaculus, posts) = recording_client();
+
+    assert!(matches!(
+        metaculus.submit_probability("8541", 0.999),
+        Err(MetaculusError::InvalidForecast(_))
+    ));
+    assert!(matches!(
+        metaculus.submit_probability("7887", 0.5),
+        Err(MetaculusError::InvalidForecast(_))
+    ));
+    assert!(matches!(
+        metaculus.submit_distribution("8541", &LogisticMixture::new(vec![logistic(0.5, 0.1, 1.0)])),
+        Err(MetaculusError::InvalidForecast(_))
+    ));
+    assert!(matches!(
+        metaculus.submit_distribution("7887", &LogisticMixture::new(vec![logistic(1.2, 0.1, 1.0)])),
+        Err(MetaculusError::InvalidForecast(_))
+    ));
+    assert!(matches!(
+        metaculus.submit_distribution("7887", &LogisticMixture::new(vec![logistic(0.5, 0.0, 1.0)])),
+        Err(MetaculusError::InvalidForecast(_))
+    ));
+    assert!(matches!(
+        metaculus.submit_distribution("7887", &LogisticMixture::new(vec![])),
+        Err(MetaculusError::InvalidForecast(_))
+    ));
+    assert!(posts.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_get_only_transport_cannot_submit() {
+    assert!(matches!(
+        fixture_client().submit_probability("8541", 0.3),
+        Err(MetaculusError::Transport(_))
+    ));
+}
+
+#[test]
+fn test_posts_client_cannot_submit() {
+    let posts = Arc::new(Mutex::new(vec![]));
+    let metaculus = Metaculus::builder()
+        .base_url("http://fixtures")
+        .token("good-token")
+        .api_version(ApiVersion::Posts)
+        .transport(RecordingTransport {
+            fixtures: fixtures(),
+            posts: posts.clone(),
+        })
+        .build()
+        .unwrap();
+
+    // Post ids are not question ids, so nothing is sent
+    assert!(matches!(
+        metaculus.submit_probability("30001", 0.3),
+        Err(MetaculusError::InvalidForecast(_))
+    ));
+    assert!(posts.lock().unwrap().is_empty());
+}
@@ -0,0 +1,79 @@
+mod utils;
+
+use crate::utils::read_q_from_file;
+use chrono::{Duration, NaiveDate};
+use metaculustetra::Prediction::NumP;
+use metaculustetra::Quartiles;
+
+#[test]
+fn test_community_timeseries() {
+    let question = read_q_from_file("probability_example");
+    let points: Vec<_> = question.community_timeseries().collect();
+    assert_eq!(points.len(), 101);
+
+    let first = &points[0];
+    assert_eq!(first.prediction, NumP(0.2));
+    assert_eq!(first.num_predictions, Some(1));
+    assert_eq!(
+        first.quartiles,
+        Some(Quartiles {
+            lower: NumP(0.2),
+            median: NumP(0.2),
+            upper: NumP(0.2),
+        })
+    );
+
+    // Each point is what the lookup returns at that time
+    assert!(points.windows(2).all(|pair| pair[0].time <= pair[1].time));
+    for point in &points {
+        assert_eq!(
+            question.get_community_prediction_before(point.time),
+            Some(point.prediction.clone())
+        );
+    }
+}
+
+#[test]
+fn test_metaculus_timeseries() {
+    let question = read_q_from_file("resolved_range_example");
+    let points: Vec<_> = question.metaculus_timeseries().collect();
+    assert_eq!(points.len(), 101);
+    assert!(points.iter().all(|point| point.quartiles.is_some()));
+    assert_eq!(
+        points.last().unwrap().prediction,
+        question.get_metaculus_prediction().unwrap()
+    );
+    assert_eq!(points[0].num_predictions, None);
+
+    assert_eq!(
+        read_q_from_file("discussion_example")
+            .metaculus_timeseries()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_resample() {
+    let question = read_q_from_file("probability_example");
+    let start = NaiveDate::from_ymd_opt(2018, 10, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let end = NaiveDate::from_ymd_opt(2019, 3, 6)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+
+    let daily = question.resample(start, end, Duration::days(1));
+    assert_eq!(daily.len(), 157);
+    assert_eq!(daily[0].time, start);
+    // Published on 11 October 2018
+    assert_eq!(daily[0].value, None);
+    assert_eq!(daily[11].value, Some(NumP(0.2)));
+    assert_eq!(daily.last().unwrap().time, end);
+    assert_eq!(daily.last().unwrap().value, Some(NumP(0.38)));
+
+    assert!(question.resample(start, end, Duration::zero()).is_empty());
+    assert!(question.resample(end, start, Duration::days(1)).is_empty());
+}
@@ -1,9 +1,146 @@
-use metaculustetra::Question;
+#![allow(dead_code)]
+
+#[cfg(feature = "async")]
+use futures::future::BoxFuture;
+#[cfg(feature = "async")]
+use metaculustetra::transport::AsyncTransport;
+use metaculustetra::transport::{HttpRequest, HttpResponse};
+#[cfg(feature = "async")]
+use metaculustetra::AsyncMetaculus;
+use metaculustetra::{Metaculus, MetaculusError, Question};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
+#[cfg(feature = "async")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "async")]
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use tokio::sync::Barrier;
 
 pub fn read_q_from_file(filename: &str) -> Question {
     let file = File::open(format!("tests/{}.json", filename)).unwrap();
     let reader = BufReader::new(file);
     serde_json::from_reader(reader).unwrap()
 }
+
+/// Asserts that `actual` is within `tolerance` of `expected`.
+pub fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+/// Reads the fixture with the given name, after making changes to its JSON with `edit`.
+pub fn read_edited_q_from_file(
+    filename: &str,
+    edit: impl FnOnce(&mut serde_json::Value),
+) -> Question {
+    let file = File::open(format!("tests/{}.json", filename)).unwrap();
+    let mut json: serde_json::Value = serde_json::from_reader(BufReader::new(file)).unwrap();
+    edit(&mut json);
+    serde_json::from_value(json).unwrap()
+}
+
+///
+/// Returns the `tests/*.json` fixtures keyed by the URL they are served at,
+/// `http://fixtures/api2/questions/{id}`.
+///
+pub fn fixtures() -> HashMap<String, String> {
+    let mut fixtures = HashMap::new();
+    for entry in std::fs::read_dir("tests").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let body = std::fs::read_to_string(&path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        if let Some(id) = json["id"].as_u64() {
+            fixtures.insert(format!("http://fixtures/api2/questions/{}", id), body);
+        }
+    }
+    fixtures
+}
+
+/// Serves the fixture at the requested URL, or a `404` if there is none.
+pub fn fixture_response(fixtures: &HashMap<String, String>, request: &HttpRequest) -> HttpResponse {
+    match fixtures.get(&request.url) {
+        Some(body) => HttpResponse {
+            status: 200,
+            body: body.clone(),
+        },
+        None => HttpResponse {
+            status: 404,
+            body: "{\"detail\":\"Not found.\"}".to_string(),
+        },
+    }
+}
+
+/// Returns a client that serves the `tests/*.json` fixtures without touching the network.
+pub fn fixture_client() -> Metaculus {
+    let fixtures = fixtures();
+
+    Metaculus::builder()
+        .base_url("http://fixtures")
+        .transport(
+            move |request: &HttpRequest| -> Result<HttpResponse, MetaculusError> {
+                Ok(fixture_response(&fixtures, request))
+            },
+        )
+        .build()
+        .unwrap()
+}
+
+/// An [AsyncTransport] that serves the fixtures and records the most requests in flight at once.
+#[cfg(feature = "async")]
+struct AsyncFixtureTransport {
+    fixtures: HashMap<String, String>,
+    in_flight: AtomicUsize,
+    max_in_flight: Arc<AtomicUsize>,
+    started: AtomicUsize,
+    rendezvous: usize,
+    barrier: Barrier,
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for AsyncFixtureTransport {
+    fn get<'a>(
+        &'a self,
+        request: &'a HttpRequest,
+    ) -> BoxFuture<'a, Result<HttpResponse, MetaculusError>> {
+        Box::pin(async move {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            if self.started.fetch_add(1, Ordering::SeqCst) < self.rendezvous {
+                self.barrier.wait().await;
+            }
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(fixture_response(&self.fixtures, request))
+        })
+    }
+}
+
+///
+/// Returns an async client that serves the `tests/*.json` fixtures, recording the most requests in
+/// flight at once in `max_in_flight`. The first `rendezvous` requests wait for each other before
+/// they are answered, so that many are known to be in flight together.
+///
+#[cfg(feature = "async")]
+pub fn async_fixture_client(max_in_flight: Arc<AtomicUsize>, rendezvous: usize) -> AsyncMetaculus {
+    Metaculus::builder()
+        .base_url("http://fixtures")
+        .async_transport(AsyncFixtureTransport {
+            fixtures: fixtures(),
+            in_flight: AtomicUsize::new(0),
+            max_in_flight,
+            started: AtomicUsize::new(0),
+            rendezvous,
+            barrier: Barrier::new(rendezvous.max(1)),
+        })
+        .build_async()
+        .unwrap()
+}